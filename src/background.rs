@@ -0,0 +1,296 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+use crate::{Canvas, Color, Tuple};
+use crate::patterns::uv::spherical_map;
+use crate::random::random_f64;
+
+
+/// What a ray sees when it misses every object in the world
+#[derive(Clone, Debug, PartialEq)]
+pub enum Background {
+    Solid(Color),
+    /// Blends from `bottom` for rays pointing straight down to `top` for
+    /// rays pointing straight up
+    Gradient { bottom: Color, top: Color },
+    /// An image wrapped around the whole scene
+    Image(EnvironmentMap),
+}
+
+impl Background {
+    pub fn gradient(bottom: Color, top: Color) -> Self {
+        Background::Gradient { bottom, top }
+    }
+
+    pub fn image(canvas: Canvas) -> Self {
+        Background::Image(EnvironmentMap::new(canvas))
+    }
+
+    /// The color seen when looking in `direction`
+    pub fn color_at(&self, direction: Tuple) -> Color {
+        match self {
+            Background::Solid(color) => *color,
+            Background::Gradient { bottom, top } => {
+                let t = (direction.normalize().y + 1.0) / 2.0;
+                *bottom * (1.0 - t) + *top * t
+            }
+            Background::Image(map) => map.color_at(direction),
+        }
+    }
+
+    /// Picks a random direction to gather light from at a surface facing
+    /// `normal`, returned with its probability density over solid angle.
+    /// Images favor their bright parts, anything else is sampled with a
+    /// cosine weighted hemisphere.
+    pub fn sample_direction(&self, normal: Tuple) -> (Tuple, f64) {
+        match self {
+            Background::Image(map) if map.has_light() => map.sample_direction(),
+            _ => cosine_weighted_direction(normal),
+        }
+    }
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Solid(Color::black())
+    }
+}
+
+/// An equirectangular image: its columns go once around the y axis and its
+/// rows from straight up to straight down, laid out like
+/// [`spherical_map`]. Keeps a table of how bright each pixel is so that
+/// lighting can be sampled where the light comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvironmentMap {
+    pub image: Arc<Canvas>,
+    /// The probability of picking each pixel, row by row
+    probabilities: Vec<f64>,
+    /// Running totals of `probabilities` over the rows, ending at 1
+    row_cdf: Vec<f64>,
+    /// Running totals of `probabilities` within each row, ending at 1
+    column_cdfs: Vec<Vec<f64>>,
+}
+
+impl EnvironmentMap {
+    pub fn new(image: Canvas) -> Self {
+        let (width, height) = (image.width, image.height);
+
+        // Rows near the poles cover less of the sphere, so they are less
+        // likely to be picked than equally bright rows near the horizon
+        let mut weights = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sin_theta = (PI * (y as f64 + 0.5) / height as f64).sin();
+
+            for x in 0..width {
+                weights.push(luminance(image.pixel_at(x, y)).max(0.0) * sin_theta);
+            }
+        }
+
+        let total: f64 = weights.iter().sum();
+        let probabilities: Vec<f64> = if total > 0.0 {
+            weights.iter().map(|weight| weight / total).collect()
+        } else {
+            vec![0.0; weights.len()]
+        };
+
+        let mut row_cdf = Vec::with_capacity(height);
+        let mut column_cdfs = Vec::with_capacity(height);
+        let mut rows_total = 0.0;
+
+        for row in probabilities.chunks(width.max(1)) {
+            let row_total: f64 = row.iter().sum();
+            rows_total += row_total;
+            row_cdf.push(rows_total);
+
+            let mut columns_total = 0.0;
+            column_cdfs.push(row.iter()
+                .map(|probability| {
+                    columns_total += probability;
+                    if row_total > 0.0 { columns_total / row_total } else { 0.0 }
+                })
+                .collect());
+        }
+
+        EnvironmentMap {
+            image: Arc::new(image),
+            probabilities,
+            row_cdf,
+            column_cdfs,
+        }
+    }
+
+    /// Whether any part of the image is brighter than black
+    pub fn has_light(&self) -> bool {
+        self.row_cdf.last().is_some_and(|total| *total > 0.0)
+    }
+
+    pub fn color_at(&self, direction: Tuple) -> Color {
+        let (u, v) = spherical_map(Tuple::point(direction.x, direction.y, direction.z));
+
+        // Pixel centers sit in the middle of the cells they cover. The image
+        // wraps around at u = 0 and 1, so its first and last columns meet.
+        let x = u * self.image.width as f64 - 0.5;
+        let y = (1.0 - v) * self.image.height as f64 - 0.5;
+
+        self.image.sample_wrapping(x, y)
+    }
+
+    /// Picks a direction with a probability proportional to the brightness
+    /// seen in that direction, returned with its probability density
+    pub fn sample_direction(&self) -> (Tuple, f64) {
+        let (width, height) = (self.image.width, self.image.height);
+
+        let row = pick(&self.row_cdf, random_f64() * self.row_cdf[height - 1]);
+        let column = pick(&self.column_cdfs[row], random_f64());
+
+        // A random spot within the pixel
+        let u = (column as f64 + random_f64()) / width as f64;
+        let from_top = (row as f64 + random_f64()) / height as f64;
+
+        // Undo the spherical mapping
+        let theta = PI * from_top;
+        let azimuth = (0.5 - u) * 2.0 * PI;
+        let sin_theta = theta.sin();
+        let direction = Tuple::vector(sin_theta * azimuth.sin(), theta.cos(), sin_theta * azimuth.cos());
+
+        if sin_theta <= 0.0 {
+            return (direction, 0.0);
+        }
+
+        // Spread the pixel's probability over the solid angle it covers
+        let probability = self.probabilities[row * width + column];
+        let pdf = probability * (width * height) as f64 / (2.0 * PI * PI * sin_theta);
+
+        (direction, pdf)
+    }
+}
+
+/// The index of the first running total above `target`
+fn pick(cdf: &[f64], target: f64) -> usize {
+    cdf.partition_point(|total| *total <= target).min(cdf.len() - 1)
+}
+
+/// How bright a color looks, weighting green the most as the eye does
+fn luminance(color: Color) -> f64 {
+    0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+}
+
+/// A direction in the hemisphere around `normal`, more likely the closer
+/// it is to the normal
+fn cosine_weighted_direction(normal: Tuple) -> (Tuple, f64) {
+    let helper = if normal.x.abs() > 0.9 {
+        Tuple::vector(0.0, 1.0, 0.0)
+    } else {
+        Tuple::vector(1.0, 0.0, 0.0)
+    };
+    let tangent = (helper * normal).normalize();
+    let bitangent = normal * tangent;
+
+    let phi = 2.0 * PI * random_f64();
+    let r2 = random_f64();
+    let r = r2.sqrt();
+    let cos_theta = (1.0 - r2).sqrt();
+
+    let direction = tangent * (r * phi.cos()) + bitangent * (r * phi.sin()) + normal * cos_theta;
+
+    (direction, cos_theta / PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::background::{Background, EnvironmentMap};
+    use crate::patterns::uv::spherical_map;
+    use crate::{Canvas, Color, Tuple};
+
+    #[test]
+    fn a_solid_background_is_the_same_in_every_direction() {
+        let background = Background::Solid(Color::new(0.2, 0.4, 0.6));
+
+        assert_eq!(Color::new(0.2, 0.4, 0.6), background.color_at(Tuple::vector(0.0, 1.0, 0.0)));
+        assert_eq!(Color::new(0.2, 0.4, 0.6), background.color_at(Tuple::vector(1.0, -1.0, 3.0)));
+        assert_eq!(Background::Solid(Color::black()), Background::default());
+    }
+
+    #[test]
+    fn a_gradient_background_blends_from_bottom_to_top() {
+        let background = Background::gradient(Color::white(), Color::new(0.0, 0.0, 1.0));
+        let cases = [
+            (Tuple::vector(0.0, -1.0, 0.0), Color::white()),
+            (Tuple::vector(0.0, 1.0, 0.0), Color::new(0.0, 0.0, 1.0)),
+            (Tuple::vector(1.0, 0.0, 0.0), Color::new(0.5, 0.5, 1.0)),
+            (Tuple::vector(0.0, 3.0, 0.0), Color::new(0.0, 0.0, 1.0)),
+        ];
+
+        for (direction, expected) in cases {
+            assert_eq!(expected, background.color_at(direction));
+        }
+    }
+
+    #[test]
+    fn an_image_background_is_looked_up_by_direction() {
+        // Columns go around the horizon starting at -z, and rows from
+        // straight up to straight down
+        let mut canvas = Canvas::new(4, 2);
+        canvas.write_pixel(0, 0, Color::red());
+        canvas.write_pixel(2, 1, Color::white());
+        let background = Background::image(canvas);
+
+        let up_behind = Tuple::vector(1.0, f64::sqrt(2.0), -1.0);
+        let down_ahead = Tuple::vector(-1.0, -f64::sqrt(2.0), 1.0);
+
+        assert_eq!(Color::red(), background.color_at(up_behind));
+        assert_eq!(Color::white(), background.color_at(down_ahead));
+    }
+
+    #[test]
+    fn an_image_background_has_no_seam_behind_the_camera() {
+        let mut canvas = Canvas::new(4, 1);
+        canvas.write_pixel(0, 0, Color::red());
+        canvas.write_pixel(3, 0, Color::blue());
+        let background = Background::image(canvas);
+
+        // Straight behind is u = 0, and a hair to one side u is nearly 1
+        let behind = background.color_at(Tuple::vector(0.0, 0.0, -1.0));
+        let beside_behind = background.color_at(Tuple::vector(-1e-9, 0.0, -1.0));
+
+        assert_eq!(Color::new(0.5, 0.0, 0.5), behind);
+        assert_eq!(Color::new(0.5, 0.0, 0.5), beside_behind);
+    }
+
+    #[test]
+    fn an_environment_map_samples_only_where_there_is_light() {
+        let mut canvas = Canvas::new(8, 4);
+        canvas.write_pixel(2, 1, Color::white());
+        let map = EnvironmentMap::new(canvas);
+
+        assert!(map.has_light());
+        for _ in 0..100 {
+            let (direction, pdf) = map.sample_direction();
+            let (u, v) = spherical_map(Tuple::point(direction.x, direction.y, direction.z));
+
+            assert!(pdf > 0.0);
+            assert!((0.25..=0.375).contains(&u));
+            assert!((0.25..=0.5).contains(&(1.0 - v)));
+        }
+    }
+
+    #[test]
+    fn a_black_environment_map_has_no_light() {
+        let map = EnvironmentMap::new(Canvas::new(4, 2));
+
+        assert!(!map.has_light());
+    }
+
+    #[test]
+    fn cosine_weighted_directions_point_away_from_the_surface() {
+        let background = Background::Solid(Color::white());
+        let normal = Tuple::vector(1.0, 0.0, 0.0);
+
+        for _ in 0..100 {
+            let (direction, pdf) = background.sample_direction(normal);
+
+            assert!((direction.magnitude() - 1.0).abs() < 1e-9);
+            assert!(direction.dot(normal) >= 0.0);
+            assert!((pdf - direction.dot(normal) / std::f64::consts::PI).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{Matrix4, Ray, Tuple};
+use crate::comparison::EPSILON;
+
+
+/// An axis-aligned box used to cheaply rule out rays that cannot hit a shape.
+/// An empty box has its minimum above its maximum and is never hit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: Tuple,
+    pub max: Tuple,
+}
+
+impl BoundingBox {
+    pub fn new(min: Tuple, max: Tuple) -> Self {
+        BoundingBox { min, max }
+    }
+
+    pub fn empty() -> Self {
+        BoundingBox::new(
+            Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY))
+    }
+
+    /// A box containing all of space, used for shapes like planes
+    pub fn infinite() -> Self {
+        BoundingBox::new(
+            Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [self.min, self.max].iter().all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite())
+    }
+
+    pub fn add_point(&mut self, point: Tuple) {
+        self.min = Tuple::point(self.min.x.min(point.x), self.min.y.min(point.y), self.min.z.min(point.z));
+        self.max = Tuple::point(self.max.x.max(point.x), self.max.y.max(point.y), self.max.z.max(point.z));
+    }
+
+    pub fn merge(&mut self, other: BoundingBox) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.add_point(other.min);
+        self.add_point(other.max);
+    }
+
+    pub fn contains_point(&self, point: Tuple) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn contains_box(&self, other: BoundingBox) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Computes the box that contains this box after it has been transformed.
+    /// Boxes reaching infinity can't be transformed corner by corner, so they
+    /// become infinite in every direction.
+    pub fn transform(&self, transform: &Matrix4) -> BoundingBox {
+        if self.is_empty() {
+            return *self;
+        }
+
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+
+        let mut transformed = BoundingBox::empty();
+
+        for x in [self.min.x, self.max.x] {
+            for y in [self.min.y, self.max.y] {
+                for z in [self.min.z, self.max.z] {
+                    transformed.add_point(*transform * Tuple::point(x, y, z));
+                }
+            }
+        }
+
+        transformed
+    }
+
+    pub fn intersects(&self, ray: Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x, self.min.x, self.max.x);
+        let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y, self.min.y, self.max.y);
+        let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z, self.min.z, self.max.z);
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        tmin <= tmax
+    }
+
+    /// Splits the box in half across its longest axis. Only finite boxes
+    /// can be split.
+    pub fn split(&self) -> (BoundingBox, BoundingBox) {
+        let dx = self.max.x - self.min.x;
+        let dy = self.max.y - self.min.y;
+        let dz = self.max.z - self.min.z;
+
+        let (mut left_max, mut right_min) = (self.max, self.min);
+
+        if dx >= dy && dx >= dz {
+            let middle = self.min.x + dx / 2.0;
+            left_max.x = middle;
+            right_min.x = middle;
+        } else if dy >= dz {
+            let middle = self.min.y + dy / 2.0;
+            left_max.y = middle;
+            right_min.y = middle;
+        } else {
+            let middle = self.min.z + dz / 2.0;
+            left_max.z = middle;
+            right_min.z = middle;
+        }
+
+        (BoundingBox::new(self.min, left_max), BoundingBox::new(right_min, self.max))
+    }
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        BoundingBox::empty()
+    }
+}
+
+/// Finds where a ray enters and leaves the slab between `min` and `max` on a
+/// single axis
+pub(crate) fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (tmin_numerator * f64::INFINITY, tmax_numerator * f64::INFINITY)
+    };
+
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::bounds::BoundingBox;
+    use crate::{Ray, rotation_x, rotation_y, Tuple};
+
+    #[test]
+    fn creating_an_empty_bounding_box() {
+        let b = BoundingBox::empty();
+
+        assert!(b.is_empty());
+        assert_eq!(f64::INFINITY, b.min.x);
+        assert_eq!(f64::NEG_INFINITY, b.max.x);
+    }
+
+    #[test]
+    fn adding_points_to_an_empty_bounding_box() {
+        let mut b = BoundingBox::empty();
+
+        b.add_point(Tuple::point(-5.0, 2.0, 0.0));
+        b.add_point(Tuple::point(7.0, 0.0, -3.0));
+
+        assert_eq!(Tuple::point(-5.0, 0.0, -3.0), b.min);
+        assert_eq!(Tuple::point(7.0, 2.0, 0.0), b.max);
+    }
+
+    #[test]
+    fn merging_bounding_boxes() {
+        let mut b1 = BoundingBox::new(Tuple::point(-5.0, -2.0, 0.0), Tuple::point(7.0, 4.0, 4.0));
+        let b2 = BoundingBox::new(Tuple::point(8.0, -7.0, -2.0), Tuple::point(14.0, 2.0, 8.0));
+
+        b1.merge(b2);
+        b1.merge(BoundingBox::empty());
+
+        assert_eq!(Tuple::point(-5.0, -7.0, -2.0), b1.min);
+        assert_eq!(Tuple::point(14.0, 4.0, 8.0), b1.max);
+    }
+
+    #[test]
+    fn checking_whether_a_box_contains_a_point_or_box() {
+        let b = BoundingBox::new(Tuple::point(5.0, -2.0, 0.0), Tuple::point(11.0, 4.0, 7.0));
+
+        assert!(b.contains_point(Tuple::point(5.0, -2.0, 0.0)));
+        assert!(b.contains_point(Tuple::point(8.0, 1.0, 3.0)));
+        assert!(!b.contains_point(Tuple::point(3.0, 0.0, 3.0)));
+        assert!(!b.contains_point(Tuple::point(8.0, 1.0, 8.0)));
+
+        assert!(b.contains_box(BoundingBox::new(Tuple::point(6.0, -1.0, 1.0), Tuple::point(10.0, 3.0, 6.0))));
+        assert!(!b.contains_box(BoundingBox::new(Tuple::point(4.0, -3.0, -1.0), Tuple::point(10.0, 3.0, 6.0))));
+    }
+
+    #[test]
+    fn transforming_a_bounding_box() {
+        let b = BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0));
+
+        let sqrt_two = f64::sqrt(2.0);
+
+        let transformed = b.transform(&(rotation_x(PI / 4.0) * rotation_y(PI / 4.0)));
+
+        assert_eq!(Tuple::point(-sqrt_two, -1.70710, -1.70710), transformed.min);
+        assert_eq!(Tuple::point(sqrt_two, 1.70710, 1.70710), transformed.max);
+    }
+
+    #[test]
+    fn transforming_an_infinite_bounding_box() {
+        let b = BoundingBox::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY));
+
+        assert_eq!(BoundingBox::infinite(), b.transform(&rotation_x(PI / 4.0)));
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_a_bounding_box() {
+        let b = BoundingBox::new(Tuple::point(5.0, -2.0, 0.0), Tuple::point(11.0, 4.0, 7.0));
+        let cases = [
+            (Tuple::point(15.0, 1.0, 2.0), Tuple::vector(-1.0, 0.0, 0.0), true),
+            (Tuple::point(-5.0, -1.0, 4.0), Tuple::vector(1.0, 0.0, 0.0), true),
+            (Tuple::point(7.0, 6.0, 5.0), Tuple::vector(0.0, -1.0, 0.0), true),
+            (Tuple::point(8.0, 2.0, 12.0), Tuple::vector(0.0, 0.0, -1.0), true),
+            (Tuple::point(8.0, 1.0, 3.5), Tuple::vector(0.0, 0.0, 1.0), true),
+            (Tuple::point(9.0, -1.0, -8.0), Tuple::vector(2.0, 4.0, 6.0), false),
+            (Tuple::point(8.0, 3.0, -4.0), Tuple::vector(6.0, 2.0, 4.0), false),
+            (Tuple::point(12.0, 5.0, 4.0), Tuple::vector(1.0, 0.0, 0.0), false),
+            (Tuple::point(8.0, 6.0, -1.0), Tuple::vector(0.0, -1.0, 0.0), false),
+        ];
+
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+
+            assert_eq!(expected, b.intersects(r));
+        }
+    }
+
+    #[test]
+    fn an_empty_bounding_box_is_never_hit() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(!BoundingBox::empty().intersects(r));
+    }
+
+    #[test]
+    fn splitting_a_bounding_box_along_its_longest_axis() {
+        let b = BoundingBox::new(Tuple::point(-1.0, -4.0, -5.0), Tuple::point(9.0, 6.0, 5.0));
+
+        let (left, right) = b.split();
+
+        assert_eq!(Tuple::point(-1.0, -4.0, -5.0), left.min);
+        assert_eq!(Tuple::point(4.0, 6.0, 5.0), left.max);
+        assert_eq!(Tuple::point(4.0, -4.0, -5.0), right.min);
+        assert_eq!(Tuple::point(9.0, 6.0, 5.0), right.max);
+    }
+
+    #[test]
+    fn splitting_a_tall_bounding_box() {
+        let b = BoundingBox::new(Tuple::point(-1.0, -2.0, -3.0), Tuple::point(5.0, 8.0, 3.0));
+
+        let (left, right) = b.split();
+
+        assert_eq!(Tuple::point(5.0, 3.0, 3.0), left.max);
+        assert_eq!(Tuple::point(-1.0, 3.0, -3.0), right.min);
+    }
+}
@@ -0,0 +1,795 @@
+use std::f64::consts::PI;
+use rayon::prelude::*;
+use crate::{Canvas, Color, Matrix4, Ray, Tuple, World};
+use crate::random::random_f64;
+use crate::sampling::{AdaptiveSampling, Filter, SampleStrategy};
+
+/// How the camera turns points on the canvas into rays
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Projection {
+    /// Rays spread out from a single point, so further objects look smaller
+    #[default]
+    Perspective,
+    /// Parallel rays from a plane `width` units across the canvas's longer
+    /// side, so objects keep their size at any distance
+    Orthographic { width: f64 },
+    /// Equidistant fisheye: a ray's angle from the view direction grows
+    /// evenly with its distance from the canvas center, reaching half the
+    /// field of view at the edges of the longer side
+    Fisheye,
+    /// A full panorama whose columns go once around the camera and whose
+    /// rows go from straight up to straight down, with the view direction
+    /// in the middle
+    Equirectangular,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Camera {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: f64,
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+    half_width: f64,
+    half_height: f64,
+    pixel_size: f64,
+    samples: usize,
+    sample_strategy: SampleStrategy,
+    filter: Filter,
+    adaptive_sampling: Option<AdaptiveSampling>,
+    aperture: f64,
+    focal_distance: f64,
+    projection: Projection,
+}
+
+impl Camera {
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Self {
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as f64 / vsize as f64;
+
+        // The longer side of the canvas spans the full field of view
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+
+        Camera {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix4::identity_matrix(),
+            inverse_transform: Matrix4::identity_matrix(),
+            half_width,
+            half_height,
+            pixel_size: (half_width * 2.0) / hsize as f64,
+            samples: 1,
+            sample_strategy: SampleStrategy::Grid,
+            filter: Filter::Box,
+            adaptive_sampling: None,
+            aperture: 0.0,
+            focal_distance: 1.0,
+            projection: Projection::Perspective,
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.inverse_transform = transform
+            .inverse()
+            .expect("Camera transform must be invertible");
+        self.transform = transform;
+        self
+    }
+
+    /// Casts `samples` rays per pixel and combines their colors, which
+    /// smooths jagged edges. Grid and jittered sampling round the count
+    /// down to a square number, as reported by `samples()`.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    pub fn with_sample_strategy(mut self, sample_strategy: SampleStrategy) -> Self {
+        self.sample_strategy = sample_strategy;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Renders a first pass with the camera's sample count, then samples
+    /// pixels that differ from a neighbor by more than `threshold` again
+    /// until they have exactly `max_samples`. The extra samples are jittered
+    /// rather than placed on a grid, so they never repeat the first pass.
+    pub fn with_adaptive_sampling(mut self, threshold: Color, max_samples: usize) -> Self {
+        self.adaptive_sampling = Some(AdaptiveSampling::new(threshold, max_samples));
+        self
+    }
+
+    /// Simulates a lens with the given `aperture` radius, focused on objects
+    /// `focal_distance` away from the camera. Everything nearer or further
+    /// is blurred, more so with a wider aperture. Blur is noisy with few
+    /// samples per pixel. Only perspective projection has depth of field.
+    /// A negative aperture counts as 0, and a focal distance that isn't
+    /// positive turns depth of field off.
+    pub fn with_depth_of_field(mut self, aperture: f64, focal_distance: f64) -> Self {
+        if focal_distance > 0.0 {
+            self.aperture = aperture.max(0.0);
+            self.focal_distance = focal_distance;
+        } else {
+            self.aperture = 0.0;
+        }
+
+        self
+    }
+
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+
+    pub fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    /// The number of rays cast per pixel by the sample strategy
+    pub fn samples(&self) -> usize {
+        self.sample_strategy.sample_count(self.samples)
+    }
+
+    pub fn aperture(&self) -> f64 {
+        self.aperture
+    }
+
+    pub fn focal_distance(&self) -> f64 {
+        self.focal_distance
+    }
+
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
+        self.ray_for_canvas_point(x as f64 + 0.5, y as f64 + 0.5)
+    }
+
+    /// The ray through a point on the canvas, measured in pixels from its
+    /// top left corner. With depth of field it starts from a random point
+    /// on the lens.
+    pub fn ray_for_canvas_point(&self, x: f64, y: f64) -> Ray {
+        if self.projection != Projection::Perspective {
+            return self.projected_ray(x, y);
+        }
+
+        if self.aperture == 0.0 {
+            return self.ray_through_lens(x, y, 0.0, 0.0);
+        }
+
+        // A uniformly distributed point on the lens disk
+        let radius = self.aperture * random_f64().sqrt();
+        let angle = 2.0 * PI * random_f64();
+
+        self.ray_through_lens(x, y, radius * angle.cos(), radius * angle.sin())
+    }
+
+    /// The ray through a point on the canvas that passes through the lens at
+    /// (`lens_x`, `lens_y`), measured from the lens center in camera space.
+    /// Every ray through the same canvas point meets the others on the focal
+    /// plane. Always uses perspective projection.
+    pub fn ray_through_lens(&self, x: f64, y: f64, lens_x: f64, lens_y: f64) -> Ray {
+        // Offset from the edge of the canvas to the point
+        let x_offset = x * self.pixel_size;
+        let y_offset = y * self.pixel_size;
+
+        // The camera looks toward -z, so +x is to the left
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        // The point in focus lies along the pinhole ray, on the focal plane
+        let distance = self.focal_distance;
+        let focus = Tuple::point(world_x * distance, world_y * distance, -distance);
+        let lens_point = Tuple::point(lens_x, lens_y, 0.0);
+
+        let origin = self.inverse_transform * lens_point;
+        let direction = (self.inverse_transform * (focus - lens_point)).normalize();
+
+        Ray::new(origin, direction)
+    }
+
+    /// The ray through a point on the canvas for projections other than
+    /// perspective
+    fn projected_ray(&self, x: f64, y: f64) -> Ray {
+        let longer_side = self.hsize.max(self.vsize) as f64;
+
+        // Offset from the center of the canvas, in pixels, with +x to the
+        // left and +y up as in camera space
+        let from_center_x = self.hsize as f64 / 2.0 - x;
+        let from_center_y = self.vsize as f64 / 2.0 - y;
+
+        let (origin, direction) = match self.projection {
+            Projection::Orthographic { width } => {
+                let scale = width / longer_side;
+                let origin = Tuple::point(from_center_x * scale, from_center_y * scale, 0.0);
+
+                (origin, Tuple::vector(0.0, 0.0, -1.0))
+            }
+            Projection::Fisheye => {
+                let distance = from_center_x.hypot(from_center_y);
+                let angle = distance / (longer_side / 2.0) * self.field_of_view / 2.0;
+
+                let direction = if distance == 0.0 {
+                    Tuple::vector(0.0, 0.0, -1.0)
+                } else {
+                    let spread = angle.sin() / distance;
+                    Tuple::vector(from_center_x * spread, from_center_y * spread, -angle.cos())
+                };
+
+                (Tuple::point(0.0, 0.0, 0.0), direction)
+            }
+            Projection::Equirectangular => {
+                let azimuth = -from_center_x / self.hsize as f64 * 2.0 * PI;
+                let polar = y / self.vsize as f64 * PI;
+
+                let direction = Tuple::vector(
+                    -polar.sin() * azimuth.sin(),
+                    polar.cos(),
+                    -polar.sin() * azimuth.cos(),
+                );
+
+                (Tuple::point(0.0, 0.0, 0.0), direction)
+            }
+            Projection::Perspective => return self.ray_through_lens(x, y, 0.0, 0.0),
+        };
+
+        Ray::new(
+            self.inverse_transform * origin,
+            (self.inverse_transform * direction).normalize(),
+        )
+    }
+
+    pub fn render(&self, world: &World) -> Canvas {
+        self.render_with_sample_counts(world).0
+    }
+
+    /// Renders the world along with a debug image of how many samples each
+    /// pixel took, from black for none to white for the most in the image
+    pub fn render_with_sample_counts(&self, world: &World) -> (Canvas, Canvas) {
+        let width = self.hsize;
+        let mut sums = vec![PixelSamples::default(); self.hsize * self.vsize];
+
+        sums.par_iter_mut().enumerate().for_each(|(i, pixel)| {
+            *pixel = self.sample_pixel(world, i % width, i / width, self.samples());
+        });
+
+        if let Some(adaptive) = self.adaptive_sampling {
+            let coarse = self.canvas_from(&sums);
+
+            sums.par_iter_mut().enumerate().for_each(|(i, pixel)| {
+                let (x, y) = (i % width, i / width);
+                let extra = adaptive.max_samples.saturating_sub(pixel.count);
+
+                if extra > 0 && self.has_contrast(&coarse, &adaptive, x, y) {
+                    pixel.add(self.sample_offsets(world, x, y, refinement_offsets(extra)));
+                }
+            });
+        }
+
+        let most_samples = sums.iter().map(|pixel| pixel.count).max().unwrap_or(0).max(1);
+        let mut sample_counts = Canvas::new(self.hsize, self.vsize);
+
+        for (i, pixel) in sums.iter().enumerate() {
+            let shade = pixel.count as f64 / most_samples as f64;
+            sample_counts.write_pixel(i % width, i / width, Color::new(shade, shade, shade));
+        }
+
+        (self.canvas_from(&sums), sample_counts)
+    }
+
+    /// Samples the area around a pixel and combines the samples with the
+    /// camera's filter. A single sample goes through the pixel center.
+    pub fn color_for_pixel(&self, world: &World, x: usize, y: usize) -> Color {
+        self.sample_pixel(world, x, y, self.samples()).color()
+    }
+
+    fn sample_pixel(&self, world: &World, x: usize, y: usize, samples: usize) -> PixelSamples {
+        if samples == 1 {
+            let color = world.color_at(self.ray_for_pixel(x, y), World::RECURSION_DEPTH);
+            let weight = self.filter.weight(0.0, 0.0);
+
+            return PixelSamples { color: color * weight, weight, count: 1 };
+        }
+
+        self.sample_offsets(world, x, y, self.sample_strategy.offsets(samples))
+    }
+
+    /// Samples a pixel at `offsets` in the unit square, which is stretched
+    /// over the camera's filter
+    fn sample_offsets(&self, world: &World, x: usize, y: usize, offsets: Vec<(f64, f64)>) -> PixelSamples {
+        let radius = self.filter.radius();
+        let mut pixel = PixelSamples::default();
+
+        for (u, v) in offsets {
+            // Spread the samples over the filter, centered on the pixel
+            let dx = (u * 2.0 - 1.0) * radius;
+            let dy = (v * 2.0 - 1.0) * radius;
+            let weight = self.filter.weight(dx, dy);
+
+            let ray = self.ray_for_canvas_point(x as f64 + 0.5 + dx, y as f64 + 0.5 + dy);
+            pixel.color = pixel.color + world.color_at(ray, World::RECURSION_DEPTH) * weight;
+            pixel.weight += weight;
+            pixel.count += 1;
+        }
+
+        pixel
+    }
+
+    /// Whether a pixel differs from any of the pixels beside, above or below it
+    fn has_contrast(&self, image: &Canvas, adaptive: &AdaptiveSampling, x: usize, y: usize) -> bool {
+        let color = image.pixel_at(x, y);
+        let neighbors = [
+            (x.checked_sub(1), Some(y)),
+            (Some(x + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), Some(y + 1)),
+        ];
+
+        neighbors.into_iter().any(|neighbor| match neighbor {
+            (Some(nx), Some(ny)) if nx < self.hsize && ny < self.vsize => {
+                adaptive.exceeds_threshold(color, image.pixel_at(nx, ny))
+            }
+            _ => false
+        })
+    }
+
+    fn canvas_from(&self, pixels: &[PixelSamples]) -> Canvas {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+
+        for (i, pixel) in pixels.iter().enumerate() {
+            image.write_pixel(i % self.hsize, i / self.hsize, pixel.color());
+        }
+
+        image
+    }
+}
+
+/// Exactly `samples` jittered offsets, with any left over after filling a
+/// square grid placed at random
+fn refinement_offsets(samples: usize) -> Vec<(f64, f64)> {
+    let mut offsets = SampleStrategy::Jittered.offsets(samples);
+    let remaining = samples.saturating_sub(offsets.len());
+
+    if remaining > 0 {
+        offsets.extend(SampleStrategy::Random.offsets(remaining));
+    }
+
+    offsets
+}
+
+/// The filter weighted sum of the samples taken for a pixel so far
+#[derive(Copy, Clone, Debug)]
+struct PixelSamples {
+    color: Color,
+    weight: f64,
+    count: usize,
+}
+
+impl Default for PixelSamples {
+    fn default() -> Self {
+        PixelSamples { color: Color::black(), weight: 0.0, count: 0 }
+    }
+}
+
+impl PixelSamples {
+    fn add(&mut self, other: PixelSamples) {
+        self.color = self.color + other.color;
+        self.weight += other.weight;
+        self.count += other.count;
+    }
+
+    fn color(&self) -> Color {
+        if self.weight.abs() < f64::EPSILON {
+            return Color::black();
+        }
+
+        self.color * (1.0 / self.weight)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::camera::{Camera, Projection, refinement_offsets};
+    use crate::{Color, Light, Material, Matrix4, rotation_y, scaling, translation, Tuple, view_transform, World};
+    use crate::comparison::ApproxEq;
+    use crate::sampling::{Filter, SampleStrategy};
+    use crate::shapes::{Cube, Shape};
+
+    #[test]
+    fn constructing_a_camera() {
+        let c = Camera::new(160, 120, PI / 2.0);
+
+        assert_eq!(160, c.hsize());
+        assert_eq!(120, c.vsize());
+        assert_eq!(PI / 2.0, c.field_of_view());
+        assert_eq!(Matrix4::identity_matrix(), c.transform());
+    }
+
+    #[test]
+    fn pixel_size_for_horizontal_canvas() {
+        let c = Camera::new(200, 125, PI / 2.0);
+
+        assert!(c.pixel_size().approx_eq_low_precision(0.01));
+    }
+
+    #[test]
+    fn pixel_size_for_vertical_canvas() {
+        let c = Camera::new(125, 200, PI / 2.0);
+
+        assert!(c.pixel_size().approx_eq_low_precision(0.01));
+    }
+
+    #[test]
+    fn ray_through_center_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.origin);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), r.direction);
+    }
+
+    #[test]
+    fn ray_through_corner_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+
+        let r = c.ray_for_pixel(0, 0);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.origin);
+        assert_eq!(Tuple::vector(0.66519, 0.33259, -0.66851), r.direction);
+    }
+
+    #[test]
+    fn ray_when_camera_is_transformed() {
+        let c = Camera::new(201, 101, PI / 2.0)
+            .with_transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+
+        let r = c.ray_for_pixel(100, 50);
+
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+
+        assert_eq!(Tuple::point(0.0, 2.0, -5.0), r.origin);
+        assert_eq!(Tuple::vector(sqrt_two, 0.0, -sqrt_two), r.direction);
+    }
+
+    #[test]
+    fn rendering_world_with_camera() {
+        let w = World::create_default_world();
+        let from = Tuple::point(0.0, 0.0, -5.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+        let c = Camera::new(11, 11, PI / 2.0).with_transform(view_transform(from, to, up));
+
+        let image = c.render(&w);
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), image.pixel_at(5, 5));
+    }
+
+    /// A white wall facing the camera 4 units away, starting at `edge` and
+    /// reaching far to the left of the view, against a black background
+    fn edge_world(edge: f64) -> World {
+        let wall = Cube::new()
+            .with_transform(translation(edge + 100.0, 0.0, -5.0) * scaling(100.0, 100.0, 1.0))
+            .with_material(Material { ambient: 1.0, diffuse: 0.0, specular: 0.0, ..Material::default() });
+
+        World::new(vec![Shape::Cube(wall)], vec![Light::point(Tuple::point(0.0, 0.0, 0.0), Color::white())])
+    }
+
+    #[test]
+    fn a_camera_takes_one_sample_per_pixel_by_default() {
+        let c = Camera::new(1, 1, PI / 2.0);
+
+        assert_eq!(1, c.samples());
+    }
+
+    #[test]
+    fn a_camera_reports_the_samples_its_strategy_takes() {
+        let c = Camera::new(1, 1, PI / 2.0).with_samples(8);
+        assert_eq!(4, c.samples());
+
+        let c = c.with_sample_strategy(SampleStrategy::Random);
+        assert_eq!(8, c.samples());
+    }
+
+    #[test]
+    fn supersampling_blends_an_edge_within_a_pixel() {
+        let w = edge_world(0.0);
+        let c = Camera::new(1, 1, PI / 2.0).with_samples(4);
+
+        let image = c.render(&w);
+
+        assert_eq!(Color::new(0.5, 0.5, 0.5), image.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn supersampling_with_a_wider_filter() {
+        let w = edge_world(0.0);
+        let c = Camera::new(1, 1, PI / 2.0)
+            .with_samples(16)
+            .with_filter(Filter::Mitchell);
+
+        assert_eq!(Color::new(0.5, 0.5, 0.5), c.color_for_pixel(&w, 0, 0));
+    }
+
+    #[test]
+    fn random_supersampling_gives_an_intermediate_color() {
+        let w = edge_world(0.0);
+        let c = Camera::new(1, 1, PI / 2.0)
+            .with_samples(64)
+            .with_sample_strategy(SampleStrategy::Random);
+
+        let color = c.color_for_pixel(&w, 0, 0);
+
+        assert!(color.r > 0.2 && color.r < 0.8);
+    }
+
+    #[test]
+    fn every_pixel_takes_the_same_samples_without_adaptive_sampling() {
+        let w = edge_world(0.0);
+        let c = Camera::new(4, 4, PI / 2.0).with_samples(4);
+
+        let (_, sample_counts) = c.render_with_sample_counts(&w);
+
+        for x in 0..4 {
+            assert_eq!(Color::white(), sample_counts.pixel_at(x, 2));
+        }
+    }
+
+    #[test]
+    fn adaptive_sampling_refines_pixels_with_contrast() {
+        let w = edge_world(0.0);
+        let c = Camera::new(4, 4, PI / 2.0)
+            .with_adaptive_sampling(Color::new(0.1, 0.1, 0.1), 10);
+
+        let (image, sample_counts) = c.render_with_sample_counts(&w);
+
+        // The wall's edge runs between the middle two columns, which get a
+        // center sample plus 9 more
+        let one_sample = Color::new(0.1, 0.1, 0.1);
+        assert_eq!(one_sample, sample_counts.pixel_at(0, 2));
+        assert_eq!(Color::white(), sample_counts.pixel_at(1, 2));
+        assert_eq!(Color::white(), sample_counts.pixel_at(2, 2));
+        assert_eq!(one_sample, sample_counts.pixel_at(3, 2));
+        assert_eq!(Color::white(), image.pixel_at(1, 2));
+        assert_eq!(Color::black(), image.pixel_at(2, 2));
+    }
+
+    #[test]
+    fn adaptive_sampling_blends_the_refined_samples_with_the_first_pass() {
+        // The edge crosses the second column, missing its center
+        let w = edge_world(0.8);
+        let c = Camera::new(4, 4, PI / 2.0)
+            .with_adaptive_sampling(Color::new(0.1, 0.1, 0.1), 10);
+
+        let image = c.render(&w);
+
+        // The center sample and the extra samples in the left two thirds of
+        // the pixel always hit the wall or its side, those in the right third
+        // may miss
+        let color = image.pixel_at(1, 2);
+        assert!((0.69..1.01).contains(&color.r));
+    }
+
+    #[test]
+    fn adaptive_sampling_does_not_repeat_the_first_pass() {
+        // The edge crosses the second column so that its 2 by 2 grid is split
+        // evenly between the wall and the background
+        let w = edge_world(1.0);
+        let coarse = Camera::new(4, 4, PI / 2.0).with_samples(4);
+        let refined = coarse.with_adaptive_sampling(Color::new(0.1, 0.1, 0.1), 9);
+
+        let (image, sample_counts) = refined.render_with_sample_counts(&w);
+
+        // 9 samples can't split evenly
+        assert_eq!(Color::new(0.5, 0.5, 0.5), coarse.render(&w).pixel_at(1, 2));
+        assert_ne!(Color::new(0.5, 0.5, 0.5), image.pixel_at(1, 2));
+        assert_eq!(Color::white(), sample_counts.pixel_at(1, 2));
+    }
+
+    #[test]
+    fn refinement_tops_up_to_the_exact_number_of_samples() {
+        for samples in [1, 4, 5, 15] {
+            assert_eq!(samples, refinement_offsets(samples).len());
+        }
+    }
+
+    #[test]
+    fn a_high_threshold_leaves_every_pixel_alone() {
+        let w = edge_world(0.0);
+        let c = Camera::new(4, 4, PI / 2.0)
+            .with_adaptive_sampling(Color::new(2.0, 2.0, 2.0), 16);
+
+        let (_, sample_counts) = c.render_with_sample_counts(&w);
+
+        assert_eq!(Color::white(), sample_counts.pixel_at(1, 2));
+        assert_eq!(Color::white(), sample_counts.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn a_camera_has_no_depth_of_field_by_default() {
+        let c = Camera::new(160, 120, PI / 2.0);
+
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(0.0, c.aperture());
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.origin);
+        assert_eq!(r.direction, c.ray_for_pixel(100, 50).direction);
+    }
+
+    #[test]
+    fn rays_through_the_lens_meet_on_the_focal_plane() {
+        let c = Camera::new(201, 101, PI / 2.0)
+            .with_transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0))
+            .with_depth_of_field(0.5, 4.0);
+        let pinhole = Camera::new(201, 101, PI / 2.0)
+            .with_transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+
+        let center = pinhole.ray_for_pixel(20, 10);
+        // The center ray reaches the focal plane after travelling further
+        // than 4 units, as it leaves the camera at an angle
+        let t = 4.0 / center.direction.dot(pinhole.ray_for_pixel(100, 50).direction);
+        let in_focus = center.position(t);
+
+        for (lens_x, lens_y) in [(0.5, 0.0), (-0.3, 0.2), (0.0, -0.5)] {
+            let r = c.ray_through_lens(20.5, 10.5, lens_x, lens_y);
+            let closest = r.direction.dot(in_focus - r.origin);
+
+            assert_eq!(in_focus, r.position(closest));
+        }
+    }
+
+    #[test]
+    fn rays_start_from_random_points_on_the_lens() {
+        let c = Camera::new(11, 11, PI / 2.0).with_depth_of_field(0.25, 3.0);
+        let origins: Vec<Tuple> = (0..50).map(|_| c.ray_for_pixel(5, 5).origin).collect();
+
+        assert!(origins.iter().all(|origin| origin.magnitude() <= 0.25));
+        assert!(origins.iter().any(|origin| origin.magnitude() > 0.0));
+    }
+
+    #[test]
+    fn only_objects_off_the_focal_plane_are_blurred() {
+        // The pixel's center looks just past the edge of the wall, whose
+        // face is 4 units away
+        let w = edge_world(-0.9);
+        let focused = Camera::new(4, 4, PI / 2.0).with_depth_of_field(0.5, 4.0);
+        let blurred = Camera::new(4, 4, PI / 2.0).with_depth_of_field(0.5, 1.0);
+
+        for (lens_x, lens_y) in [(-0.5, 0.0), (0.0, 0.0), (0.5, 0.0), (0.0, 0.5)] {
+            let r = focused.ray_through_lens(2.5, 2.5, lens_x, lens_y);
+
+            assert_eq!(Color::black(), w.color_at(r, World::RECURSION_DEPTH));
+        }
+
+        let r = blurred.ray_through_lens(2.5, 2.5, -0.5, 0.0);
+
+        assert_eq!(Color::white(), w.color_at(r, World::RECURSION_DEPTH));
+    }
+
+    #[test]
+    fn invalid_lens_settings_leave_the_image_sharp() {
+        let negative_aperture = Camera::new(4, 4, PI / 2.0).with_depth_of_field(-0.5, 2.0);
+        let zero_focus = Camera::new(4, 4, PI / 2.0).with_depth_of_field(0.5, 0.0);
+        let negative_focus = Camera::new(4, 4, PI / 2.0)
+            .with_depth_of_field(0.5, 2.0)
+            .with_depth_of_field(0.5, -2.0);
+
+        assert_eq!((0.0, 2.0), (negative_aperture.aperture(), negative_aperture.focal_distance()));
+        assert_eq!((0.0, 1.0), (zero_focus.aperture(), zero_focus.focal_distance()));
+        assert_eq!((0.0, 2.0), (negative_focus.aperture(), negative_focus.focal_distance()));
+
+        let r = zero_focus.ray_for_pixel(0, 0);
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.origin);
+        assert!(r.direction.magnitude().approx_eq(1.0));
+    }
+
+    #[test]
+    fn a_camera_uses_perspective_projection_by_default() {
+        let c = Camera::new(160, 120, PI / 2.0);
+
+        assert_eq!(Projection::Perspective, c.projection());
+        assert_eq!(Projection::Perspective, Projection::default());
+    }
+
+    #[test]
+    fn orthographic_rays_are_parallel() {
+        let c = Camera::new(11, 11, PI / 2.0)
+            .with_projection(Projection::Orthographic { width: 2.0 });
+
+        let center = c.ray_for_pixel(5, 5);
+        let corner = c.ray_for_pixel(0, 0);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), center.origin);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), center.direction);
+        assert_eq!(Tuple::point(10.0 / 11.0, 10.0 / 11.0, 0.0), corner.origin);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), corner.direction);
+    }
+
+    #[test]
+    fn orthographic_rays_follow_the_camera_transform() {
+        let c = Camera::new(11, 11, PI / 2.0)
+            .with_projection(Projection::Orthographic { width: 2.0 })
+            .with_transform(rotation_y(PI / 2.0) * translation(0.0, -2.0, 5.0));
+
+        let r = c.ray_for_pixel(5, 5);
+
+        assert_eq!(Tuple::point(0.0, 2.0, -5.0), r.origin);
+        assert_eq!(Tuple::vector(1.0, 0.0, 0.0), r.direction);
+    }
+
+    #[test]
+    fn fisheye_angles_grow_evenly_from_the_center() {
+        let c = Camera::new(201, 101, PI).with_projection(Projection::Fisheye);
+        let half_way = f64::sqrt(2.0) / 2.0;
+
+        let cases = [
+            (100.5, 50.5, Tuple::vector(0.0, 0.0, -1.0)),
+            (0.0, 50.5, Tuple::vector(1.0, 0.0, 0.0)),
+            (201.0, 50.5, Tuple::vector(-1.0, 0.0, 0.0)),
+            (150.75, 50.5, Tuple::vector(-half_way, 0.0, -half_way)),
+            (100.5, 0.25, Tuple::vector(0.0, half_way, -half_way)),
+        ];
+
+        for (x, y, direction) in cases {
+            let r = c.ray_for_canvas_point(x, y);
+
+            assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.origin);
+            assert_eq!(direction, r.direction);
+        }
+    }
+
+    #[test]
+    fn equirectangular_rays_cover_every_direction() {
+        let c = Camera::new(200, 100, PI / 2.0).with_projection(Projection::Equirectangular);
+
+        let cases = [
+            (100.0, 50.0, Tuple::vector(0.0, 0.0, -1.0)),
+            (150.0, 50.0, Tuple::vector(-1.0, 0.0, 0.0)),
+            (50.0, 50.0, Tuple::vector(1.0, 0.0, 0.0)),
+            (0.0, 50.0, Tuple::vector(0.0, 0.0, 1.0)),
+            (100.0, 0.0, Tuple::vector(0.0, 1.0, 0.0)),
+            (100.0, 100.0, Tuple::vector(0.0, -1.0, 0.0)),
+        ];
+
+        for (x, y, direction) in cases {
+            assert_eq!(direction, c.ray_for_canvas_point(x, y).direction);
+        }
+    }
+
+    #[test]
+    fn only_perspective_projection_has_depth_of_field() {
+        let c = Camera::new(11, 11, PI / 2.0)
+            .with_projection(Projection::Equirectangular)
+            .with_depth_of_field(0.5, 2.0);
+
+        assert!((0..20).all(|_| c.ray_for_pixel(3, 4).origin == Tuple::point(0.0, 0.0, 0.0)));
+    }
+}
@@ -1,6 +1,7 @@
 use std::io;
-use std::io::{Write};
-use image::ImageResult;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use image::{ImageError, ImageResult};
 use crate::color::Color;
 
 #[derive(Debug, PartialEq)]
@@ -35,6 +36,122 @@ impl Canvas {
         Color::black()
     }
 
+    /// Samples the canvas between pixel centers by blending the four nearest
+    /// pixels. Coordinates outside the canvas are clamped to its edges.
+    pub fn sample(&self, x: f64, y: f64) -> Color {
+        if self.width == 0 || self.height == 0 {
+            return Color::black();
+        }
+
+        let x = x.clamp(0.0, (self.width - 1) as f64);
+        let x0 = x.floor() as usize;
+
+        self.sample_columns(x0, (x0 + 1).min(self.width - 1), x - x0 as f64, y)
+    }
+
+    /// Like [`Canvas::sample`], but wraps around horizontally so the left
+    /// and right edges blend into each other, for images that go all the
+    /// way round
+    pub fn sample_wrapping(&self, x: f64, y: f64) -> Color {
+        if self.width == 0 || self.height == 0 {
+            return Color::black();
+        }
+
+        let tx = x - x.floor();
+        let x0 = (x.floor() as i64).rem_euclid(self.width as i64) as usize;
+
+        self.sample_columns(x0, (x0 + 1) % self.width, tx, y)
+    }
+
+    /// Blends columns `x0` and `x1`, `tx` of the way from one to the other,
+    /// at row `y` clamped to the canvas
+    fn sample_columns(&self, x0: usize, x1: usize, tx: f64, y: f64) -> Color {
+        let y = y.clamp(0.0, (self.height - 1) as f64);
+        let y0 = y.floor() as usize;
+        let y1 = (y0 + 1).min(self.height - 1);
+        let ty = y - y0 as f64;
+
+        let top = self.pixel_at(x0, y0) * (1.0 - tx) + self.pixel_at(x1, y0) * tx;
+        let bottom = self.pixel_at(x0, y1) * (1.0 - tx) + self.pixel_at(x1, y1) * tx;
+
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    /// Parses a plain (P3) PPM image. Comments and any amount of whitespace
+    /// between values are allowed, and colors are scaled by the maximum value
+    /// given in the header.
+    pub fn from_ppm(ppm: &str) -> io::Result<Self> {
+        let mut tokens = ppm.lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .flat_map(|line| line.split_whitespace());
+
+        if tokens.next() != Some("P3") {
+            return Err(invalid_ppm("expected the magic number P3"));
+        }
+
+        let mut next_number = |name: &str| -> io::Result<usize> {
+            tokens.next()
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| invalid_ppm(&format!("expected a number for the {}", name)))
+        };
+
+        let width = next_number("width")?;
+        let height = next_number("height")?;
+        let max = next_number("maximum color value")?;
+
+        if max == 0 {
+            return Err(invalid_ppm("the maximum color value must be positive"));
+        }
+
+        let expected_values = width.checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(|| invalid_ppm("the image is too large"))?;
+
+        // Check the data is all there before allocating anything for it
+        let values: Vec<&str> = tokens.collect();
+        if values.len() < expected_values {
+            return Err(invalid_ppm("expected a number for every color component"));
+        }
+
+        let component = |value: &str| -> io::Result<f64> {
+            value.parse::<usize>()
+                .map(|value| value as f64 / max as f64)
+                .map_err(|_| invalid_ppm("expected a number for every color component"))
+        };
+
+        let mut canvas = Canvas::new(width, height);
+
+        for (i, rgb) in values[..expected_values].chunks(3).enumerate() {
+            canvas.pixels[i] = Color::new(component(rgb[0])?, component(rgb[1])?, component(rgb[2])?);
+        }
+
+        Ok(canvas)
+    }
+
+    /// Loads an image file into a canvas. PPM files are read with
+    /// [`Canvas::from_ppm`], anything else (PNG, JPEG, HDR, ...) with the
+    /// `image` crate. HDR images keep colors brighter than white.
+    pub fn from_file(path: impl AsRef<Path>) -> ImageResult<Self> {
+        let path = path.as_ref();
+        let is_ppm = path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("ppm"));
+
+        if is_ppm {
+            let ppm = std::fs::read_to_string(path).map_err(ImageError::IoError)?;
+            return Canvas::from_ppm(&ppm).map_err(ImageError::IoError);
+        }
+
+        let img = image::open(path)?.into_rgb32f();
+        let mut canvas = Canvas::new(img.width() as usize, img.height() as usize);
+
+        for (x, y, pixel) in img.enumerate_pixels() {
+            let [r, g, b] = pixel.0;
+            canvas.write_pixel(x as usize, y as usize, Color::new(r as f64, g as f64, b as f64));
+        }
+
+        Ok(canvas)
+    }
+
     pub fn pixels(&mut self) -> &mut Vec<Color> {
         &mut self.pixels
     }
@@ -101,6 +218,10 @@ pub fn convert_f32_to_u8(component: f64) -> u8 {
     (component * 255.0) as u8
 }
 
+fn invalid_ppm(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, format!("Invalid PPM: {}", message))
+}
+
 fn convert_color_u8(color: &Color) -> (u8, u8, u8) {
     (
         convert_f32_to_u8(color.r),
@@ -150,4 +271,146 @@ mod tests {
         canvas.export_ppm(r#"C:\tmp"#).expect("Couldn't create file");
         assert_eq!(header, expected)
     }
+
+    #[test]
+    fn reading_a_file_with_the_wrong_magic_number() {
+        let ppm = "P32\n1 1\n255\n0 0 0\n";
+
+        assert_eq!(ErrorKind::InvalidData, Canvas::from_ppm(ppm).unwrap_err().kind());
+    }
+
+    #[test]
+    fn reading_a_ppm_returns_a_canvas_of_the_right_size() {
+        let ppm = "P3\n10 2\n255\n".to_string() + &"0 0 0 ".repeat(20);
+
+        let canvas = Canvas::from_ppm(&ppm).unwrap();
+
+        assert_eq!(10, canvas.width);
+        assert_eq!(2, canvas.height);
+    }
+
+    #[test]
+    fn reading_pixel_data_from_a_ppm_file() {
+        let ppm = "P3
+4 3
+255
+255 127 0  0 127 255  127 255 0  255 255 255
+0 0 0  255 0 0  0 255 0  0 0 255
+255 255 0  0 255 255  255 0 255  127 127 127
+";
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+        let cases = [
+            (0, 0, Color::new(1.0, 0.49803, 0.0)),
+            (1, 0, Color::new(0.0, 0.49803, 1.0)),
+            (2, 0, Color::new(0.49803, 1.0, 0.0)),
+            (3, 0, Color::new(1.0, 1.0, 1.0)),
+            (0, 1, Color::new(0.0, 0.0, 0.0)),
+            (1, 1, Color::new(1.0, 0.0, 0.0)),
+            (2, 1, Color::new(0.0, 1.0, 0.0)),
+            (3, 1, Color::new(0.0, 0.0, 1.0)),
+            (0, 2, Color::new(1.0, 1.0, 0.0)),
+            (1, 2, Color::new(0.0, 1.0, 1.0)),
+            (2, 2, Color::new(1.0, 0.0, 1.0)),
+            (3, 2, Color::new(0.49803, 0.49803, 0.49803)),
+        ];
+
+        for (x, y, expected) in cases {
+            assert_eq!(expected, canvas.pixel_at(x, y));
+        }
+    }
+
+    #[test]
+    fn ppm_parsing_ignores_comment_lines() {
+        let ppm = "P3
+# this is a comment
+2 1
+# this, too
+255
+# another comment
+255 255 255
+# oh, no, comments in the pixel data!
+255 0 255
+";
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(Color::white(), canvas.pixel_at(0, 0));
+        assert_eq!(Color::new(1.0, 0.0, 1.0), canvas.pixel_at(1, 0));
+    }
+
+    #[test]
+    fn ppm_parsing_allows_an_rgb_triple_to_span_lines() {
+        let ppm = "P3\n1 1\n255\n51\n153\n\n204\n";
+
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(Color::new(0.2, 0.6, 0.8), canvas.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn ppm_parsing_respects_the_scale_setting() {
+        let ppm = "P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(Color::new(0.75, 0.5, 0.25), canvas.pixel_at(0, 1));
+    }
+
+    #[test]
+    fn reading_a_truncated_ppm_fails() {
+        let ppm = "P3\n2 1\n255\n255 255 255 0 0\n";
+
+        assert_eq!(ErrorKind::InvalidData, Canvas::from_ppm(ppm).unwrap_err().kind());
+    }
+
+    #[test]
+    fn reading_a_ppm_with_a_zero_maximum_color_value_fails() {
+        let ppm = "P3\n1 1\n0\n0 0 0\n";
+
+        assert_eq!(ErrorKind::InvalidData, Canvas::from_ppm(ppm).unwrap_err().kind());
+    }
+
+    #[test]
+    fn reading_a_ppm_with_an_impossible_size_fails() {
+        let overflowing = format!("P3\n{} 2\n255\n0 0 0\n", usize::MAX);
+        let too_large = "P3\n100000 100000\n255\n0 0 0\n";
+
+        assert_eq!(ErrorKind::InvalidData, Canvas::from_ppm(&overflowing).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidData, Canvas::from_ppm(too_large).unwrap_err().kind());
+    }
+
+    #[test]
+    fn exported_images_can_be_loaded_back() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(0, 0, Color::red());
+        canvas.write_pixel(2, 1, Color::white());
+        let path = std::env::temp_dir().join("canvas_round_trip.png");
+
+        canvas.export(path.to_str().unwrap()).unwrap();
+        let loaded = Canvas::from_file(&path).unwrap();
+
+        assert_eq!(canvas, loaded);
+    }
+
+    #[test]
+    fn sampling_blends_the_nearest_pixels() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(1, 0, Color::white());
+        canvas.write_pixel(1, 1, Color::white());
+
+        assert_eq!(Color::black(), canvas.sample(0.0, 0.0));
+        assert_eq!(Color::new(0.5, 0.5, 0.5), canvas.sample(0.5, 0.5));
+        assert_eq!(Color::new(0.25, 0.25, 0.25), canvas.sample(0.25, 1.0));
+        assert_eq!(Color::white(), canvas.sample(3.0, -1.0));
+    }
+
+    #[test]
+    fn wrapped_sampling_blends_across_the_left_and_right_edges() {
+        let mut canvas = Canvas::new(4, 1);
+        canvas.write_pixel(3, 0, Color::white());
+
+        assert_eq!(Color::new(0.5, 0.5, 0.5), canvas.sample_wrapping(-0.5, 0.0));
+        assert_eq!(Color::new(0.5, 0.5, 0.5), canvas.sample_wrapping(3.5, 0.0));
+        assert_eq!(Color::white(), canvas.sample_wrapping(7.0, 0.0));
+        assert_eq!(Color::black(), canvas.sample(-0.5, 0.0));
+    }
 }
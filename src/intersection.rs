@@ -1,56 +1,97 @@
 use std::ops::Index;
 use crate::{Ray, Tuple};
-use crate::shapes::shape_enum::{RayInteractable, Shape};
+use crate::comparison::EPSILON;
+use crate::shapes::shape_enum::RayInteractable;
 
+/// A hit on a specific shape. `object` refers to the primitive that was hit,
+/// even when that primitive is nested inside a group.
 #[derive(Copy, Clone, Debug)]
-pub struct Intersection {
+pub struct Intersection<'a> {
     pub t: f64,
-    pub object: Shape,
+    pub object: &'a dyn RayInteractable,
+    pub u: Option<f64>,
+    pub v: Option<f64>,
 }
 
-impl Intersection {
-    pub fn new(t: f64, obj: Shape) -> Self {
-        Intersection { t, object: obj }
+impl<'a> Intersection<'a> {
+    pub fn new(t: f64, obj: &'a dyn RayInteractable) -> Self {
+        Intersection { t, object: obj, u: None, v: None }
     }
 
-    pub fn prepare_computations(&self, ray: Ray) -> Computations {
+    /// Creates an intersection that also records where on the surface the
+    /// hit landed, as used by triangles
+    pub fn new_with_uv(t: f64, obj: &'a dyn RayInteractable, u: f64, v: f64) -> Self {
+        Intersection { t, object: obj, u: Some(u), v: Some(v) }
+    }
+
+    /// Precomputes the values needed to shade this hit. `xs` holds every
+    /// intersection along `ray`, including this one, and is used to find the
+    /// refractive indices on either side of the surface.
+    pub fn prepare_computations(&self, ray: Ray, xs: &Intersections<'a>) -> Computations<'a> {
         let point = ray.position(self.t);
         let eye_v = -ray.direction;
 
-        let shape = match self.object {
-            Shape::Sphere(sphere) => { sphere }
+        let normal_v = self.object.normal_at_hit(point, self);
+
+        let (n1, n2) = self.refractive_indices(xs);
+
+        Computations::new(self.t, self.object, point, eye_v, normal_v, n1, n2)
+    }
+
+    /// Walks the intersections up to this one, keeping track of which objects
+    /// the ray is inside of, to find the refractive index of the material
+    /// being left (`n1`) and the one being entered (`n2`)
+    fn refractive_indices(&self, xs: &Intersections<'a>) -> (f64, f64) {
+        let mut containers: Vec<&dyn RayInteractable> = vec![];
+        let mut n1 = 1.0;
+        let mut n2 = 1.0;
+
+        let outermost_index = |containers: &Vec<&dyn RayInteractable>| {
+            containers.last().map_or(1.0, |object| object.material().refractive_index)
         };
 
-        let normal_v = shape.normal_at(point);
+        for i in xs.iter() {
+            let is_hit = i.t == self.t && std::ptr::addr_eq(i.object, self.object);
 
+            if is_hit {
+                n1 = outermost_index(&containers);
+            }
 
-        let comps = Computations::new(self.t, self.object, point, eye_v, normal_v);
+            match containers.iter().position(|object| std::ptr::addr_eq(*object, i.object)) {
+                Some(index) => { containers.remove(index); }
+                None => containers.push(i.object)
+            }
 
+            if is_hit {
+                n2 = outermost_index(&containers);
+                break;
+            }
+        }
 
-        comps
+        (n1, n2)
     }
 }
 
 
-impl PartialEq for Intersection {
+impl PartialEq for Intersection<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.t == other.t
     }
 }
 
 #[derive(Debug)]
-pub struct Intersections {
-    intersections: Vec<Intersection>,
+pub struct Intersections<'a> {
+    intersections: Vec<Intersection<'a>>,
 }
 
-impl Intersections {
+impl<'a> Intersections<'a> {
     pub fn new() -> Self {
         Self {
             intersections: Vec::<Intersection>::new(),
         }
     }
 
-    pub fn new_from_vec(mut vec: Vec<Intersection>) -> Self {
+    pub fn new_from_vec(mut vec: Vec<Intersection<'a>>) -> Self {
         vec.sort_unstable_by(|a, b| a.t.partial_cmp(&b.t).unwrap());
 
         Intersections { intersections: vec }
@@ -64,12 +105,12 @@ impl Intersections {
         self.intersections.is_empty()
     }
 
-    pub fn push(&mut self, i: Intersection) {
+    pub fn push(&mut self, i: Intersection<'a>) {
         self.intersections.push(i);
         self.sort()
     }
 
-    pub fn push_vec(&mut self, vec: Vec<Intersection>) {
+    pub fn push_vec(&mut self, vec: Vec<Intersection<'a>>) {
         for i in vec {
             self.intersections.push(i);
         }
@@ -81,13 +122,30 @@ impl Intersections {
         self.intersections.sort_unstable_by(|a, b| a.t.partial_cmp(&b.t).unwrap());
     }
 
-    pub fn hit(&self) -> Option<&Intersection> {
+    /// Keeps only the intersections for which `keep` returns true, visiting
+    /// them in order
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&Intersection<'a>) -> bool,
+    {
+        self.intersections.retain(keep);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a>> {
+        self.intersections.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<Intersection<'a>> {
+        self.intersections
+    }
+
+    pub fn hit(&self) -> Option<&Intersection<'a>> {
         self.intersections.iter().find(|i| i.t >= 0.0)
     }
 }
 
-impl<'a> Index<usize> for Intersections {
-    type Output = Intersection;
+impl<'a> Index<usize> for Intersections<'a> {
+    type Output = Intersection<'a>;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.intersections[index]
@@ -95,17 +153,24 @@ impl<'a> Index<usize> for Intersections {
 }
 
 
-pub struct Computations {
+pub struct Computations<'a> {
     pub t: f64,
-    pub object: Shape,
+    pub object: &'a dyn RayInteractable,
     pub point: Tuple,
     pub eye_v: Tuple,
     pub normal_v: Tuple,
     pub inside: bool,
+    pub over_point: Tuple,
+    pub under_point: Tuple,
+    pub reflect_v: Tuple,
+    /// Refractive index of the material the ray is leaving
+    pub n1: f64,
+    /// Refractive index of the material the ray is entering
+    pub n2: f64,
 }
 
-impl Computations {
-    pub fn new(t: f64, object: Shape, point: Tuple, eye_v: Tuple, normal_v: Tuple) -> Self {
+impl<'a> Computations<'a> {
+    pub fn new(t: f64, object: &'a dyn RayInteractable, point: Tuple, eye_v: Tuple, normal_v: Tuple, n1: f64, n2: f64) -> Self {
         let mut is_inside = false;
         let mut normal_v = normal_v;
 
@@ -114,6 +179,16 @@ impl Computations {
             normal_v = -normal_v;
         }
 
+        // Nudge the point slightly above the surface so that floating point
+        // error does not make the surface cast a shadow on itself
+        let over_point = point + normal_v * EPSILON;
+
+        // Refracted rays start just below the surface instead
+        let under_point = point - normal_v * EPSILON;
+
+        // The incoming ray is the opposite of the eye vector
+        let reflect_v = (-eye_v).reflect(normal_v);
+
         Computations {
             t,
             object,
@@ -121,7 +196,34 @@ impl Computations {
             eye_v,
             normal_v,
             inside: is_inside,
+            over_point,
+            under_point,
+            reflect_v,
+            n1,
+            n2,
+        }
+    }
+
+    /// Schlick's approximation of the Fresnel effect: the fraction of light
+    /// that is reflected rather than refracted at this hit
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eye_v.dot(self.normal_v);
+
+        // Total internal reflection can only happen when leaving a denser material
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+
+            cos = (1.0 - sin2_t).sqrt();
         }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
     }
 }
 
@@ -129,22 +231,34 @@ impl Computations {
 #[cfg(test)]
 mod tests {
     use crate::shapes::sphere::Sphere;
+    use crate::shapes::plane::Plane;
+    use crate::shapes::triangle::Triangle;
     use crate::intersection::{Intersection, Intersections};
-    use crate::{Ray, Tuple};
+    use crate::{Material, Ray, scaling, translation, Tuple};
+    use crate::comparison::{ApproxEq, EPSILON};
     use crate::shapes::shape_enum::Shape;
 
     #[test]
     fn intersection_encapsulates_t_and_object() {
         let s = Sphere::new();
 
-        let i = Intersection::new(3.5, Shape::Sphere(s));
-
-        let expected_s = match i.object {
-            Shape::Sphere(sphere) => sphere
-        };
+        let i = Intersection::new(3.5, &s);
 
         assert_eq!(3.5, i.t);
-        assert_eq!(s, expected_s);
+        assert!(std::ptr::addr_eq(&s, i.object));
+    }
+
+    #[test]
+    fn intersection_can_encapsulate_u_and_v() {
+        let s = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+
+        let i = Intersection::new_with_uv(3.5, &s, 0.2, 0.4);
+
+        assert_eq!(Some(0.2), i.u);
+        assert_eq!(Some(0.4), i.v);
     }
 
     #[test]
@@ -152,8 +266,8 @@ mod tests {
         let s = Sphere::new();
         let shape = Shape::Sphere(s);
 
-        let i1 = Intersection::new(1.0, shape);
-        let i2 = Intersection::new(2.0, shape);
+        let i1 = Intersection::new(1.0, &shape);
+        let i2 = Intersection::new(2.0, &shape);
 
         let mut xs = Intersections::new();
         xs.push(i1);
@@ -169,8 +283,8 @@ mod tests {
         let s = Sphere::new();
         let shape = Shape::Sphere(s);
 
-        let i1 = Intersection::new(1.0, shape);
-        let i2 = Intersection::new(2.0, shape);
+        let i1 = Intersection::new(1.0, &shape);
+        let i2 = Intersection::new(2.0, &shape);
 
         let xs = Intersections::new_from_vec(vec![i1, i2]);
 
@@ -182,8 +296,8 @@ mod tests {
         let s = Sphere::new();
         let shape = Shape::Sphere(s);
 
-        let i1 = Intersection::new(-1.0, shape);
-        let i2 = Intersection::new(1.0, shape);
+        let i1 = Intersection::new(-1.0, &shape);
+        let i2 = Intersection::new(1.0, &shape);
 
         let xs = Intersections::new_from_vec(vec![i1, i2]);
 
@@ -196,8 +310,8 @@ mod tests {
         let s = Sphere::new();
         let shape = Shape::Sphere(s);
 
-        let i1 = Intersection::new(-2.0, shape);
-        let i2 = Intersection::new(-1.0, shape);
+        let i1 = Intersection::new(-2.0, &shape);
+        let i2 = Intersection::new(-1.0, &shape);
 
         let xs = Intersections::new_from_vec(vec![i1, i2]);
 
@@ -210,10 +324,10 @@ mod tests {
         let s = Sphere::new();
         let shape = Shape::Sphere(s);
 
-        let i1 = Intersection::new(5.0, shape);
-        let i2 = Intersection::new(7.0, shape);
-        let i3 = Intersection::new(-3.0, shape);
-        let i4 = Intersection::new(2.0, shape);
+        let i1 = Intersection::new(5.0, &shape);
+        let i2 = Intersection::new(7.0, &shape);
+        let i3 = Intersection::new(-3.0, &shape);
+        let i4 = Intersection::new(2.0, &shape);
 
         let xs = Intersections::new_from_vec(vec![i1, i2, i3, i4]);
 
@@ -225,9 +339,9 @@ mod tests {
     fn precomputing_state_of_an_intersection() {
         let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
         let shape = Sphere::new();
-        let i = Intersection::new(4.0, Shape::Sphere(shape));
+        let i = Intersection::new(4.0, &shape);
 
-        let comps = i.prepare_computations(r);
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
 
         assert_eq!(i.t, comps.t);
         assert_eq!(Tuple::point(0.0, 0.0, -1.0), comps.point);
@@ -242,8 +356,8 @@ mod tests {
             Tuple::vector(0.0, 0.0, 1.0));
 
         let shape = Sphere::new();
-        let i = Intersection::new(4.0, Shape::Sphere(shape));
-        let comps = i.prepare_computations(r);
+        let i = Intersection::new(4.0, &shape);
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
 
         assert_eq!(false, comps.inside)
     }
@@ -255,12 +369,140 @@ mod tests {
             Tuple::vector(0.0, 0.0, 1.0));
 
         let shape = Sphere::new();
-        let i = Intersection::new(1.0, Shape::Sphere(shape));
-        let comps = i.prepare_computations(r);
+        let i = Intersection::new(1.0, &shape);
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
 
         assert_eq!(Tuple::point(0.0, 0.0, 1.0), comps.point);
         assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.eye_v);
         assert_eq!(true, comps.inside);
         assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.normal_v)
     }
+
+    #[test]
+    fn hit_should_offset_the_point() {
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::vector(0.0, 0.0, 1.0));
+
+        let shape = Sphere::new().with_transform(translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &shape);
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+
+        assert!(comps.over_point.z < -EPSILON / 2.0);
+        assert!(comps.point.z > comps.over_point.z);
+    }
+
+    #[test]
+    fn precomputing_state_of_a_plane_intersection() {
+        let r = Ray::new(
+            Tuple::point(0.0, 1.0, -1.0),
+            Tuple::vector(0.0, -1.0, 0.0));
+
+        let shape = Plane::new();
+        let i = Intersection::new(1.0, &shape);
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+
+        assert_eq!(Tuple::point(0.0, 0.0, -1.0), comps.point);
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), comps.normal_v);
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn precomputing_the_reflection_vector() {
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let shape = Plane::new();
+        let r = Ray::new(
+            Tuple::point(0.0, 1.0, -1.0),
+            Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let i = Intersection::new(f64::sqrt(2.0), &shape);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+
+        assert_eq!(Tuple::vector(0.0, sqrt_two, sqrt_two), comps.reflect_v);
+    }
+
+    #[test]
+    fn finding_n1_and_n2_at_various_intersections() {
+        let a = Sphere::new()
+            .with_transform(scaling(2.0, 2.0, 2.0))
+            .with_material(Material { refractive_index: 1.5, ..Material::glass() });
+        let b = Sphere::new()
+            .with_transform(translation(0.0, 0.0, -0.25))
+            .with_material(Material { refractive_index: 2.0, ..Material::glass() });
+        let c = Sphere::new()
+            .with_transform(translation(0.0, 0.0, 0.25))
+            .with_material(Material { refractive_index: 2.5, ..Material::glass() });
+        let r = Ray::new(Tuple::point(0.0, 0.0, -4.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ]);
+        let expected = [(1.0, 1.5), (1.5, 2.0), (2.0, 2.5), (2.5, 2.5), (2.5, 1.5), (1.5, 1.0)];
+
+        for (index, (n1, n2)) in expected.into_iter().enumerate() {
+            let comps = xs[index].prepare_computations(r, &xs);
+
+            assert_eq!(n1, comps.n1);
+            assert_eq!(n2, comps.n2);
+        }
+    }
+
+    #[test]
+    fn under_point_is_offset_below_the_surface() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let shape = Sphere::new()
+            .with_transform(translation(0.0, 0.0, 1.0))
+            .with_material(Material::glass());
+        let i = Intersection::new(5.0, &shape);
+        let xs = Intersections::new_from_vec(vec![i]);
+
+        let comps = i.prepare_computations(r, &xs);
+
+        assert!(comps.under_point.z > EPSILON / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+
+    #[test]
+    fn schlick_approximation_under_total_internal_reflection() {
+        let shape = Sphere::new().with_material(Material::glass());
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, sqrt_two), Tuple::vector(0.0, 1.0, 0.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(-sqrt_two, &shape),
+            Intersection::new(sqrt_two, &shape),
+        ]);
+
+        let comps = xs[1].prepare_computations(r, &xs);
+
+        assert_eq!(1.0, comps.schlick());
+    }
+
+    #[test]
+    fn schlick_approximation_with_a_perpendicular_viewing_angle() {
+        let shape = Sphere::new().with_material(Material::glass());
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(-1.0, &shape),
+            Intersection::new(1.0, &shape),
+        ]);
+
+        let comps = xs[1].prepare_computations(r, &xs);
+
+        assert!(0.04.approx_eq_low_precision(comps.schlick()));
+    }
+
+    #[test]
+    fn schlick_approximation_with_small_angle_and_n2_greater_than_n1() {
+        let shape = Sphere::new().with_material(Material::glass());
+        let r = Ray::new(Tuple::point(0.0, 0.99, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = Intersections::new_from_vec(vec![Intersection::new(1.8589, &shape)]);
+
+        let comps = xs[0].prepare_computations(r, &xs);
+
+        assert!(0.48873.approx_eq_low_precision(comps.schlick()));
+    }
 }
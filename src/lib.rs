@@ -5,9 +5,14 @@ pub mod matrices;
 pub mod transformation;
 pub mod ray;
 pub mod intersection;
-mod lights;
+pub mod bounds;
+pub mod camera;
+pub mod obj_parser;
+pub mod background;
+pub mod sampling;
 mod materials;
 mod world;
+mod random;
 
 pub use tuple::Tuple;
 pub use canvas::Canvas;
@@ -17,15 +22,72 @@ pub use transformation::*;
 pub use ray::*;
 pub use materials::Material;
 pub use lights::Light;
+pub use world::World;
+pub use camera::{Camera, Projection};
+pub use bounds::BoundingBox;
+pub use background::Background;
+
+pub mod lights {
+    pub mod point;
+    pub mod area;
+    pub mod directional;
+    pub mod spot;
+    pub mod light_enum;
+
+    pub use point::{Attenuation, PointLight};
+    pub use area::AreaLight;
+    pub use directional::DirectionalLight;
+    pub use spot::SpotLight;
+    pub use light_enum::{Light, LightSample, LightSource};
+}
 
 pub mod shapes {
     pub mod sphere;
+    pub mod plane;
+    pub mod cube;
+    pub mod cylinder;
+    pub mod cone;
+    pub mod triangle;
+    pub mod smooth_triangle;
+    pub mod group;
+    pub mod csg;
     pub mod shape_enum;
 
     pub use sphere::Sphere;
+    pub use plane::Plane;
+    pub use cube::Cube;
+    pub use cylinder::Cylinder;
+    pub use cone::Cone;
+    pub use triangle::Triangle;
+    pub use smooth_triangle::SmoothTriangle;
+    pub use group::Group;
+    pub use csg::{Csg, CsgOperation};
     pub use shape_enum::Shape;
 }
 
+pub mod patterns {
+    pub mod stripe;
+    pub mod gradient;
+    pub mod ring;
+    pub mod checker;
+    pub mod blend;
+    pub mod perturbed;
+    pub mod perlin;
+    pub mod uv;
+    pub mod texture_map;
+    pub mod pattern_enum;
+
+    pub use stripe::Stripe;
+    pub use gradient::Gradient;
+    pub use ring::Ring;
+    pub use checker::Checker;
+    pub use blend::Blend;
+    pub use perturbed::Perturbed;
+    pub use uv::{CubeFace, UvMapping, UvPattern};
+    pub use texture_map::TextureMap;
+    pub use pattern_enum::{ColorPattern, Pattern};
+}
+
 pub mod comparison {
     pub mod epsilon;
     pub mod approx_eq;
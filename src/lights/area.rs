@@ -0,0 +1,194 @@
+use crate::{Color, Tuple};
+use crate::lights::light_enum::{LightSample, LightSource};
+use crate::random::random_f64;
+
+/// A rectangular light of `usteps` by `vsteps` cells, each sampled
+/// separately, which gives soft shadows
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AreaLight {
+    /// The center of the light
+    pub position: Tuple,
+    pub intensity: Color,
+    pub corner: Tuple,
+    /// The edge of a single cell along the first side of the rectangle
+    pub uvec: Tuple,
+    pub usteps: usize,
+    /// The edge of a single cell along the second side of the rectangle
+    pub vvec: Tuple,
+    pub vsteps: usize,
+    /// Samples a random point in each cell instead of its center, which
+    /// trades the banding of evenly spaced samples for noise
+    pub jitter: bool,
+}
+
+impl AreaLight {
+    /// A light with one corner at `corner` and sides `full_uvec` and
+    /// `full_vvec`, divided into `usteps` by `vsteps` jittered cells
+    pub fn new(corner: Tuple, full_uvec: Tuple, usteps: usize, full_vvec: Tuple, vsteps: usize, intensity: Color) -> Self {
+        let usteps = usteps.max(1);
+        let vsteps = vsteps.max(1);
+
+        AreaLight {
+            position: corner + full_uvec * 0.5 + full_vvec * 0.5,
+            intensity,
+            corner,
+            uvec: full_uvec / usteps as f64,
+            usteps,
+            vvec: full_vvec / vsteps as f64,
+            vsteps,
+            jitter: true,
+        }
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The number of points the light is sampled at
+    pub fn sample_count(&self) -> usize {
+        self.usteps * self.vsteps
+    }
+
+    /// The point in cell (`u`, `v`) at the given offsets within the cell,
+    /// both between 0 and 1
+    pub fn point_on_light(&self, u: usize, v: usize, u_offset: f64, v_offset: f64) -> Tuple {
+        self.corner
+            + self.uvec * (u as f64 + u_offset)
+            + self.vvec * (v as f64 + v_offset)
+    }
+
+    /// One point in every cell of the light, at its center or at a random
+    /// spot within it when jittering
+    pub fn sample_points(&self) -> Vec<Tuple> {
+        let mut points = Vec::with_capacity(self.sample_count());
+
+        for v in 0..self.vsteps {
+            for u in 0..self.usteps {
+                let point = if self.jitter {
+                    self.point_on_light(u, v, random_f64(), random_f64())
+                } else {
+                    self.point_on_light(u, v, 0.5, 0.5)
+                };
+
+                points.push(point);
+            }
+        }
+
+        points
+    }
+}
+
+impl LightSource for AreaLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples(&self, point: Tuple) -> Vec<LightSample> {
+        self.sample_points()
+            .into_iter()
+            .map(|position| {
+                let v = position - point;
+
+                LightSample {
+                    direction: v.normalize(),
+                    distance: v.magnitude(),
+                    intensity: self.intensity,
+                }
+            })
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Tuple};
+    use crate::lights::{AreaLight, LightSource};
+
+    #[test]
+    fn creating_an_area_light() {
+        let corner = Tuple::point(0.0, 0.0, 0.0);
+        let v1 = Tuple::vector(2.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 0.0, 1.0);
+        let light = AreaLight::new(corner, v1, 4, v2, 2, Color::white());
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), light.corner);
+        assert_eq!(Tuple::vector(0.5, 0.0, 0.0), light.uvec);
+        assert_eq!(4, light.usteps);
+        assert_eq!(Tuple::vector(0.0, 0.0, 0.5), light.vvec);
+        assert_eq!(2, light.vsteps);
+        assert_eq!(8, light.sample_count());
+        assert_eq!(Tuple::point(1.0, 0.0, 0.5), light.position);
+    }
+
+    #[test]
+    fn finding_a_single_point_on_an_area_light() {
+        let corner = Tuple::point(0.0, 0.0, 0.0);
+        let v1 = Tuple::vector(2.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 0.0, 1.0);
+        let light = AreaLight::new(corner, v1, 4, v2, 2, Color::white());
+        let cases = [
+            (0, 0, Tuple::point(0.25, 0.0, 0.25)),
+            (1, 0, Tuple::point(0.75, 0.0, 0.25)),
+            (0, 1, Tuple::point(0.25, 0.0, 0.75)),
+            (2, 0, Tuple::point(1.25, 0.0, 0.25)),
+            (3, 1, Tuple::point(1.75, 0.0, 0.75)),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, light.point_on_light(u, v, 0.5, 0.5));
+        }
+    }
+
+    #[test]
+    fn finding_a_single_point_on_a_jittered_area_light() {
+        let corner = Tuple::point(0.0, 0.0, 0.0);
+        let v1 = Tuple::vector(2.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 0.0, 1.0);
+        let light = AreaLight::new(corner, v1, 4, v2, 2, Color::white());
+        let cases = [
+            (0, 0, Tuple::point(0.15, 0.0, 0.35)),
+            (1, 0, Tuple::point(0.65, 0.0, 0.35)),
+            (0, 1, Tuple::point(0.15, 0.0, 0.85)),
+            (2, 0, Tuple::point(1.15, 0.0, 0.35)),
+            (3, 1, Tuple::point(1.65, 0.0, 0.85)),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, light.point_on_light(u, v, 0.3, 0.7));
+        }
+    }
+
+    #[test]
+    fn jittered_samples_stay_within_their_cells() {
+        let corner = Tuple::point(0.0, 0.0, 0.0);
+        let v1 = Tuple::vector(2.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 0.0, 1.0);
+        let light = AreaLight::new(corner, v1, 4, v2, 2, Color::white());
+
+        let points = light.sample_points();
+
+        assert_eq!(8, points.len());
+        for (i, point) in points.iter().enumerate() {
+            let (u, v) = ((i % 4) as f64, (i / 4) as f64);
+
+            assert!((u * 0.5..=(u + 1.0) * 0.5).contains(&point.x));
+            assert!((v * 0.5..=(v + 1.0) * 0.5).contains(&point.z));
+        }
+    }
+
+    #[test]
+    fn an_area_light_is_sampled_once_per_cell() {
+        let corner = Tuple::point(0.0, 0.0, 0.0);
+        let v1 = Tuple::vector(2.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 0.0, 1.0);
+        let light = AreaLight::new(corner, v1, 4, v2, 2, Color::white()).with_jitter(false);
+
+        let samples = light.samples(Tuple::point(0.25, 3.0, 0.25));
+
+        assert_eq!(8, samples.len());
+        assert_eq!(Tuple::vector(0.0, -1.0, 0.0), samples[0].direction);
+        assert_eq!(3.0, samples[0].distance);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{Color, Tuple};
+use crate::lights::light_enum::{LightSample, LightSource};
+
+/// A light so far away, like the sun, that its rays arrive in parallel from
+/// the same direction everywhere in the scene
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DirectionalLight {
+    /// The direction the light travels in
+    pub direction: Tuple,
+    pub intensity: Color,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Tuple, intensity: Color) -> Self {
+        DirectionalLight {
+            direction: direction.normalize(),
+            intensity,
+        }
+    }
+}
+
+impl LightSource for DirectionalLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples(&self, _point: Tuple) -> Vec<LightSample> {
+        vec![LightSample {
+            direction: -self.direction,
+            distance: f64::INFINITY,
+            intensity: self.intensity,
+        }]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Tuple};
+    use crate::lights::{DirectionalLight, LightSource};
+
+    #[test]
+    fn a_directional_light_shines_the_same_way_everywhere() {
+        let light = DirectionalLight::new(Tuple::vector(0.0, -2.0, 0.0), Color::white());
+
+        for point in [Tuple::point(0.0, 0.0, 0.0), Tuple::point(10.0, -5.0, 3.0)] {
+            let samples = light.samples(point);
+
+            assert_eq!(1, samples.len());
+            assert_eq!(Tuple::vector(0.0, 1.0, 0.0), samples[0].direction);
+            assert_eq!(f64::INFINITY, samples[0].distance);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Debug;
+use crate::{Color, Tuple};
+use crate::lights::area::AreaLight;
+use crate::lights::directional::DirectionalLight;
+use crate::lights::point::PointLight;
+use crate::lights::spot::SpotLight;
+
+
+/// How a light reaches a point: the direction towards the light, how far
+/// away it is, and the intensity that arrives
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LightSample {
+    /// Normalized vector from the lit point towards the light
+    pub direction: Tuple,
+    /// Distance to the light along `direction`, infinite for lights that are
+    /// infinitely far away
+    pub distance: f64,
+    pub intensity: Color,
+}
+
+pub trait LightSource: Debug {
+    /// The color and brightness of the light at its source, which also
+    /// drives the ambient term everywhere in the scene, even where the light
+    /// itself doesn't reach
+    fn intensity(&self) -> Color;
+
+    /// Samples the light as seen from `point`. Lights with an area return
+    /// one sample per cell; the others return a single sample.
+    fn samples(&self, point: Tuple) -> Vec<LightSample>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Light {
+    Point(PointLight),
+    Area(AreaLight),
+    Directional(DirectionalLight),
+    Spot(SpotLight),
+}
+
+impl Light {
+    pub fn point(position: Tuple, intensity: Color) -> Self {
+        Light::Point(PointLight::new(position, intensity))
+    }
+
+    pub fn area(corner: Tuple, full_uvec: Tuple, usteps: usize, full_vvec: Tuple, vsteps: usize, intensity: Color) -> Self {
+        Light::Area(AreaLight::new(corner, full_uvec, usteps, full_vvec, vsteps, intensity))
+    }
+
+    pub fn directional(direction: Tuple, intensity: Color) -> Self {
+        Light::Directional(DirectionalLight::new(direction, intensity))
+    }
+
+    pub fn spot(position: Tuple, direction: Tuple, inner_angle: f64, outer_angle: f64, intensity: Color) -> Self {
+        Light::Spot(SpotLight::new(position, direction, inner_angle, outer_angle, intensity))
+    }
+
+    fn as_light_source(&self) -> &dyn LightSource {
+        match self {
+            Light::Point(light) => light,
+            Light::Area(light) => light,
+            Light::Directional(light) => light,
+            Light::Spot(light) => light,
+        }
+    }
+}
+
+impl LightSource for Light {
+    fn intensity(&self) -> Color {
+        self.as_light_source().intensity()
+    }
+
+    fn samples(&self, point: Tuple) -> Vec<LightSample> {
+        self.as_light_source().samples(point)
+    }
+}
+
+impl Default for Light {
+    fn default() -> Self {
+        Light::Point(PointLight::default())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{Color, Tuple};
+use crate::lights::light_enum::{LightSample, LightSource};
+
+/// How a light dims with distance `d`: its intensity is divided by
+/// `constant + linear * d + quadratic * d * d`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Attenuation {
+    pub constant: f64,
+    pub linear: f64,
+    pub quadratic: f64,
+}
+
+impl Attenuation {
+    pub fn new(constant: f64, linear: f64, quadratic: f64) -> Self {
+        Attenuation { constant, linear, quadratic }
+    }
+
+    /// The same brightness at any distance
+    pub fn none() -> Self {
+        Attenuation::new(1.0, 0.0, 0.0)
+    }
+
+    /// The physically correct falloff, where the intensity is what arrives
+    /// one unit away from the light
+    pub fn inverse_square() -> Self {
+        Attenuation::new(0.0, 0.0, 1.0)
+    }
+
+    /// The fraction of the light's intensity that arrives `distance` away
+    pub fn factor(&self, distance: f64) -> f64 {
+        1.0 / (self.constant + self.linear * distance + self.quadratic * distance * distance)
+    }
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Attenuation::none()
+    }
+}
+
+/// A light that shines equally in every direction from a single point
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointLight {
+    pub position: Tuple,
+    pub intensity: Color,
+    pub attenuation: Attenuation,
+}
+
+impl PointLight {
+    pub fn new(position: Tuple, intensity: Color) -> Self {
+        PointLight {
+            position,
+            intensity,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+}
+
+impl LightSource for PointLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples(&self, point: Tuple) -> Vec<LightSample> {
+        let v = self.position - point;
+        let distance = v.magnitude();
+
+        vec![LightSample {
+            direction: v.normalize(),
+            distance,
+            intensity: self.intensity * self.attenuation.factor(distance),
+        }]
+    }
+}
+
+impl Default for PointLight {
+    fn default() -> Self {
+        PointLight {
+            intensity: Color::white(),
+            position: Tuple::point(0.0, 0.0, 0.0),
+            attenuation: Attenuation::none(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Tuple};
+    use crate::comparison::ApproxEq;
+    use crate::lights::{Attenuation, LightSource, PointLight};
+
+    #[test]
+    fn light_has_position_and_intensity() {
+        let intensity = Color::new(1.0, 1.0, 1.0);
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let light = PointLight::new(position, intensity);
+
+        assert_eq!(light.position, position);
+        assert_eq!(light.intensity, intensity)
+    }
+
+    #[test]
+    fn a_point_light_has_a_single_sample_towards_its_position() {
+        let light = PointLight::new(Tuple::point(0.0, 3.0, 4.0), Color::white());
+
+        let samples = light.samples(Tuple::point(0.0, 0.0, 0.0));
+
+        assert_eq!(1, samples.len());
+        assert_eq!(Tuple::vector(0.0, 0.6, 0.8), samples[0].direction);
+        assert_eq!(5.0, samples[0].distance);
+        assert_eq!(Color::white(), samples[0].intensity);
+    }
+
+    #[test]
+    fn point_lights_do_not_dim_with_distance_by_default() {
+        let light = PointLight::new(Tuple::point(0.0, 0.0, 0.0), Color::white());
+
+        assert_eq!(Attenuation::none(), light.attenuation);
+        assert_eq!(Color::white(), light.samples(Tuple::point(0.0, 0.0, 100.0))[0].intensity);
+    }
+
+    #[test]
+    fn computing_attenuation_factors() {
+        let cases = [
+            (Attenuation::none(), 10.0, 1.0),
+            (Attenuation::inverse_square(), 1.0, 1.0),
+            (Attenuation::inverse_square(), 2.0, 0.25),
+            (Attenuation::inverse_square(), 10.0, 0.01),
+            (Attenuation::new(1.0, 0.09, 0.032), 10.0, 0.196078),
+            (Attenuation::new(1.0, 0.7, 1.8), 1.0, 0.285714),
+            (Attenuation::new(0.5, 0.5, 0.0), 3.0, 0.5),
+        ];
+
+        for (attenuation, distance, expected) in cases {
+            assert!(expected.approx_eq_low_precision(attenuation.factor(distance)));
+        }
+    }
+
+    #[test]
+    fn an_attenuated_point_light_dims_with_distance() {
+        let light = PointLight::new(Tuple::point(0.0, 0.0, 0.0), Color::new(4.0, 4.0, 4.0))
+            .with_attenuation(Attenuation::inverse_square());
+
+        let near = light.samples(Tuple::point(0.0, 1.0, 0.0))[0];
+        let far = light.samples(Tuple::point(0.0, 4.0, 0.0))[0];
+
+        assert_eq!(Color::new(4.0, 4.0, 4.0), near.intensity);
+        assert_eq!(Color::new(0.25, 0.25, 0.25), far.intensity);
+    }
+}
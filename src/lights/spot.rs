@@ -0,0 +1,143 @@
+use crate::{Color, Tuple};
+use crate::lights::light_enum::{LightSample, LightSource};
+
+/// A point light that only shines within a cone. Points inside the inner
+/// cone get the full intensity, which fades to nothing at the outer cone.
+/// The ambient term isn't limited to the cone: like that of any other light
+/// it stands in for light bounced around the scene, so it reaches every
+/// point at the full intensity.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpotLight {
+    pub position: Tuple,
+    /// The axis of the cone, pointing away from the light
+    pub direction: Tuple,
+    /// Angle in radians between the axis and the edge of the fully lit cone
+    pub inner_angle: f64,
+    /// Angle in radians between the axis and the edge of the light
+    pub outer_angle: f64,
+    /// Shapes the fade between the cones: 1 fades linearly in the cosine of
+    /// the angle, higher values fade out faster
+    pub falloff: f64,
+    pub intensity: Color,
+}
+
+impl SpotLight {
+    pub fn new(position: Tuple, direction: Tuple, inner_angle: f64, outer_angle: f64, intensity: Color) -> Self {
+        SpotLight {
+            position,
+            direction: direction.normalize(),
+            inner_angle,
+            outer_angle: outer_angle.max(inner_angle),
+            falloff: 1.0,
+            intensity,
+        }
+    }
+
+    pub fn with_falloff(mut self, falloff: f64) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// How much of the light reaches `point`, from 0 outside the outer cone
+    /// to 1 inside the inner cone. The light's own position has no direction
+    /// from it, and counts as inside the inner cone.
+    pub fn cone_factor(&self, point: Tuple) -> f64 {
+        let v = point - self.position;
+        if v.magnitude() == 0.0 {
+            return 1.0;
+        }
+
+        let cos_angle = v.normalize().dot(self.direction);
+        let cos_inner = self.inner_angle.cos();
+        let cos_outer = self.outer_angle.cos();
+
+        if cos_angle >= cos_inner {
+            1.0
+        } else if cos_angle <= cos_outer {
+            0.0
+        } else {
+            ((cos_angle - cos_outer) / (cos_inner - cos_outer)).powf(self.falloff)
+        }
+    }
+}
+
+impl LightSource for SpotLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples(&self, point: Tuple) -> Vec<LightSample> {
+        let v = self.position - point;
+
+        vec![LightSample {
+            direction: v.normalize(),
+            distance: v.magnitude(),
+            intensity: self.intensity * self.cone_factor(point),
+        }]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::{Color, Tuple};
+    use crate::lights::{LightSource, SpotLight};
+
+    #[test]
+    fn a_spot_light_fully_lights_points_inside_its_inner_cone() {
+        let light = SpotLight::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::white());
+
+        let samples = light.samples(Tuple::point(0.1, 0.0, 0.0));
+
+        assert_eq!(1, samples.len());
+        assert_eq!(Color::white(), samples[0].intensity);
+    }
+
+    #[test]
+    fn a_spot_light_does_not_light_points_outside_its_outer_cone() {
+        let light = SpotLight::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::white());
+
+        assert_eq!(0.0, light.cone_factor(Tuple::point(2.0, 0.0, 0.0)));
+        assert_eq!(0.0, light.cone_factor(Tuple::point(0.0, 2.0, 0.0)));
+        assert_eq!(Color::black(), light.samples(Tuple::point(0.0, 0.0, 2.0))[0].intensity);
+    }
+
+    #[test]
+    fn a_spot_light_fully_lights_its_own_position() {
+        let light = SpotLight::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::white());
+
+        assert_eq!(1.0, light.cone_factor(light.position));
+    }
+
+    #[test]
+    fn a_spot_light_fades_between_its_cones() {
+        let light = SpotLight::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::white());
+        // Halfway between the cosines of the inner and outer angles
+        let cos_middle = ((PI / 8.0).cos() + (PI / 4.0).cos()) / 2.0;
+        let point = Tuple::point((1.0 - cos_middle * cos_middle).sqrt() / cos_middle, 0.0, 0.0);
+
+        assert!((light.cone_factor(point) - 0.5).abs() < 1e-9);
+        assert!((light.with_falloff(2.0).cone_factor(point) - 0.25).abs() < 1e-9);
+    }
+}
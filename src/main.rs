@@ -1,14 +1,10 @@
 use std::time::Instant;
 use ray_tracer_challenge::*;
-use ray_tracer_challenge::intersection::Intersections;
 use ray_tracer_challenge::shapes::{Shape, Sphere};
 use std::f64::consts::PI;
 use std::fs::{OpenOptions};
 use std::io;
 use std::io::Write;
-use std::sync::Mutex;
-use rayon::prelude::*;
-use ray_tracer_challenge::shapes::shape_enum::*;
 
 fn main() -> io::Result<()> {
     let size = 2048;
@@ -33,54 +29,22 @@ fn main() -> io::Result<()> {
 
 
 fn cast_ray_at_sphere(size: usize) {
-    let canvas_pixels = size;
-    let wall_size = 7.0;
-    let wall_z = 10.0;
-    let pixel_size = wall_size / canvas_pixels as f64;
-    let half = wall_size / 2.0;
+    let sphere = Sphere::new()
+        .with_transform(rotation_x(PI / 6.0) * scaling(1.0, 0.8, 1.1))
+        .with_material(Material { color: Color::new(1.0, 0.0, 1.0), ..Material::default() });
 
-    let mut canvas_mutex = Mutex::new(Canvas::new(canvas_pixels, canvas_pixels));
-    let mut sphere = Sphere::new().with_transform(rotation_x(PI / 6.0) * scaling(1.0, 0.8, 1.1));
-    let ray_origin = Tuple::point(0.0, 0.0, -5.0);
+    let light = Light::point(Tuple::point(10.0, 0.0, -10.0), Color::white());
+    let world = World::new(vec![Shape::Sphere(sphere)], vec![light]);
 
-    // Chapter 6 additions
-    sphere.material.color = Color::new(1.0, 0.0, 1.0);
-    let light_position = Tuple::point(10.0, 0.0, -10.0);
-    let light_color = Color::white();
-    let light = Light::new(light_position, light_color);
+    let camera = Camera::new(size, size, PI / 3.0)
+        .with_transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0)));
 
-    (0..canvas_pixels).into_par_iter().for_each(|y| {
-        (0..canvas_pixels).into_par_iter().for_each(|x| {
-            let world_y = half - pixel_size * (y as f64);
-            let world_x = -half + pixel_size * (x as f64);
-
-            let position = Tuple::point(world_x, world_y, wall_z);
-
-            let ray_direction = (position - ray_origin).normalize();
-            let ray = Ray::new(ray_origin, ray_direction);
-
-            let xs = Intersections::new_from_vec(sphere.intersect(ray));
-
-            if let Some(hit) = xs.hit() {
-                let shape = hit.object;
-
-                let point = ray.position(hit.t);
-                let normal = shape.normal_at(point);
-                let eye = -ray.direction;
-
-                let color = shape.material().lighting(light, point, eye, normal);
-
-                let mut canvas = canvas_mutex.lock().unwrap();
-                canvas.write_pixel(x, y, color);
-            }
-        });
-    });
-
-    let mut canvas = canvas_mutex.lock().unwrap();
+    let canvas = camera.render(&world);
 
     canvas.export(r#"C:\tmp\output.png"#).expect("Couldn't create image");
-
-    drop(canvas)
 }
 
 
@@ -1,13 +1,27 @@
 use crate::{Color, Tuple};
-use crate::lights::Light;
+use crate::lights::{Light, LightSample, LightSource};
+use crate::patterns::{ColorPattern, Pattern};
+use crate::shapes::shape_enum::RayInteractable;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Material {
     pub color: Color,
+    /// Varies the color across the surface. Takes the place of `color`
+    /// when set.
+    pub pattern: Option<Pattern>,
     pub ambient: f64,
     pub diffuse: f64,
     pub specular: f64,
-    pub shininess: f64
+    pub shininess: f64,
+    /// How much of the surrounding scene the surface reflects, from 0 for
+    /// a matte surface to 1 for a perfect mirror
+    pub reflective: f64,
+    /// How much light passes through the surface, from 0 for opaque to 1
+    /// for fully transparent
+    pub transparency: f64,
+    /// How much light bends when entering the material, such as 1.0 for a
+    /// vacuum, 1.333 for water and 1.52 for glass
+    pub refractive_index: f64,
 }
 
 impl Material {
@@ -15,31 +29,56 @@ impl Material {
         Material::default()
     }
 
-    pub fn lighting(&self, light: Light, point: Tuple, eye_v: Tuple, normal_v: Tuple) -> Color {
-        let diffuse;
-        let specular;
-        let ambient;
+    /// A clear glass material
+    pub fn glass() -> Self {
+        Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Material::default()
+        }
+    }
+
+    pub fn with_pattern(mut self, pattern: Pattern) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
 
-        // Combine the surface color with the light's color/intensity
-        let effective_color = self.color * light.intensity;
+    /// The color of the surface of `object` at a world space point
+    pub fn color_at(&self, object: &dyn RayInteractable, point: Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.color_at_object(object, point),
+            None => self.color
+        }
+    }
 
-        // Find the direction to the light source
-        let light_v = (light.position - point).normalize();
+    /// Shades a point lit by `samples` of `light`, in which any samples
+    /// blocked by another object have been turned black
+    pub fn lighting_samples(&self, object: &dyn RayInteractable, light: Light, point: Tuple, eye_v: Tuple, normal_v: Tuple, samples: &[LightSample]) -> Color {
+        let surface_color = self.color_at(object, point);
 
         // Compute the ambient contribution
-        ambient = effective_color * self.ambient;
+        let ambient = surface_color * light.intensity() * self.ambient;
+
+        if samples.is_empty() {
+            return ambient;
+        }
+
+        let lit = samples.iter().fold(Color::black(), |sum, sample| {
+            // Combine the surface color with the light's color/intensity
+            let effective_color = surface_color * sample.intensity;
+            let light_v = sample.direction;
 
-        // light_dot_normal represent the cosine of the angle between the
-        // light vector and the normal vector. A negative number means the
-        // light is on the other side of the surface.
-        let light_dot_normal = light_v.dot(normal_v);
+            // light_dot_normal represent the cosine of the angle between the
+            // light vector and the normal vector. A negative number means the
+            // light is on the other side of the surface.
+            let light_dot_normal = light_v.dot(normal_v);
+
+            if light_dot_normal < 0.0 {
+                return sum;
+            }
 
-        if light_dot_normal < 0.0 {
-            diffuse = Color::black();
-            specular = Color::black();
-        } else {
             // Compute the diffuse contribution
-            diffuse = effective_color * self.diffuse * light_dot_normal;
+            let diffuse = effective_color * self.diffuse * light_dot_normal;
 
             // reflect_dot_eye represent the cosine of the angle between the
             // reflection vector and the eye vector. A negative number means the
@@ -47,16 +86,18 @@ impl Material {
             let reflect_v = -light_v.reflect(normal_v);
             let reflect_dot_eye = reflect_v.dot(eye_v);
 
-            if reflect_dot_eye <= 0.0 {
-                specular = Color::black();
+            let specular = if reflect_dot_eye <= 0.0 {
+                Color::black()
             } else {
                 // Compute the specular contribution
                 let factor = reflect_dot_eye.powf(self.shininess);
-                specular = light.intensity * self.specular * factor;
-            }
-        }
+                sample.intensity * self.specular * factor
+            };
+
+            sum + diffuse + specular
+        });
 
-        ambient + diffuse + specular
+        ambient + lit * (1.0 / samples.len() as f64)
     }
 }
 
@@ -64,10 +105,14 @@ impl Default for Material {
     fn default() -> Self {
         Material {
             color: Color::white(),
+            pattern: None,
             ambient: 0.1,
             diffuse: 0.9,
             specular: 0.9,
             shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
         }
     }
 }
@@ -75,8 +120,10 @@ impl Default for Material {
 #[cfg(test)]
 mod tests {
     use crate::{Color, Tuple};
-    use crate::lights::Light;
+    use crate::lights::{AreaLight, Attenuation, Light, LightSample, LightSource, PointLight};
     use crate::Material;
+    use crate::patterns::{Pattern, Stripe};
+    use crate::shapes::Sphere;
 
     #[test]
     fn default_material() {
@@ -87,6 +134,9 @@ mod tests {
         assert_eq!(m.diffuse, 0.9);
         assert_eq!(m.specular, 0.9);
         assert_eq!(m.shininess, 200.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.refractive_index, 1.0);
     }
 
     #[test]
@@ -96,9 +146,9 @@ mod tests {
 
         let eye_v = Tuple::vector(0.0, 0.0, -1.0);
         let normal_v = Tuple::vector(0.0, 0.0, -1.0);
-        let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::white());
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
 
-        let result = m.lighting(light, position, eye_v, normal_v);
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
 
         assert_eq!(result, Color::new(1.9, 1.9, 1.9))
     }
@@ -111,9 +161,9 @@ mod tests {
 
         let eye_v = Tuple::vector(0.0, sqrt_two, sqrt_two);
         let normal_v = Tuple::vector(0.0, 0.0, -1.0);
-        let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::white());
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
 
-        let result = m.lighting(light, position, eye_v, normal_v);
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
 
         assert_eq!(result, Color::new(1.0, 1.0, 1.0))
     }
@@ -125,9 +175,9 @@ mod tests {
 
         let eye_v = Tuple::vector(0.0, 0.0, -1.0);
         let normal_v = Tuple::vector(0.0, 0.0, -1.0);
-        let light = Light::new(Tuple::point(0.0, 10.0, -10.0), Color::white());
+        let light = Light::point(Tuple::point(0.0, 10.0, -10.0), Color::white());
 
-        let result = m.lighting(light, position, eye_v, normal_v);
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
 
         assert_eq!(result, Color::new(0.7364, 0.7364, 0.7364))
     }
@@ -140,9 +190,9 @@ mod tests {
 
         let eye_v = Tuple::vector(0.0, -sqrt_two, -sqrt_two);
         let normal_v = Tuple::vector(0.0, 0.0, -1.0);
-        let light = Light::new(Tuple::point(0.0, 10.0, -10.0), Color::white());
+        let light = Light::point(Tuple::point(0.0, 10.0, -10.0), Color::white());
 
-        let result = m.lighting(light, position, eye_v, normal_v);
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
 
         assert_eq!(result, Color::new(1.6364, 1.6364, 1.6364))
     }
@@ -154,10 +204,139 @@ mod tests {
 
         let eye_v = Tuple::vector(0.0, 0.0, -1.0);
         let normal_v = Tuple::vector(0.0, 0.0, -1.0);
-        let light = Light::new(Tuple::point(0.0, 0.0, 10.0), Color::white());
+        let light = Light::point(Tuple::point(0.0, 0.0, 10.0), Color::white());
 
-        let result = m.lighting(light, position, eye_v, normal_v);
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
 
         assert_eq!(result, Color::new(0.1, 0.1, 0.1))
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn lighting_with_the_surface_in_shadow() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye_v = Tuple::vector(0.0, 0.0, -1.0);
+        let normal_v = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
+        let blocked = LightSample { intensity: Color::black(), ..light.samples(position)[0] };
+
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &[blocked]);
+
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1))
+    }
+
+    #[test]
+    fn lighting_without_samples() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye_v = Tuple::vector(0.0, 0.0, -1.0);
+        let normal_v = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
+
+        let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &[]);
+
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn lighting_with_a_pattern_applied() {
+        let m = Material {
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        }.with_pattern(Pattern::Stripe(Stripe::new(Color::white(), Color::black())));
+
+        let eye_v = Tuple::vector(0.0, 0.0, -1.0);
+        let normal_v = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
+        let object = Sphere::new();
+
+        let (p1, p2) = (Tuple::point(0.9, 0.0, 0.0), Tuple::point(1.1, 0.0, 0.0));
+
+        let c1 = m.lighting_samples(&object, light, p1, eye_v, normal_v, &light.samples(p1));
+        let c2 = m.lighting_samples(&object, light, p2, eye_v, normal_v, &light.samples(p2));
+
+        assert_eq!(Color::white(), c1);
+        assert_eq!(Color::black(), c2);
+    }
+
+    #[test]
+    fn lighting_uses_light_intensity_to_attenuate_color() {
+        let m = Material {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let position = Tuple::point(0.0, 0.0, -1.0);
+        let eye_v = Tuple::vector(0.0, 0.0, -1.0);
+        let normal_v = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white());
+        let cases = [
+            (1.0, Color::new(1.0, 1.0, 1.0)),
+            (0.5, Color::new(0.55, 0.55, 0.55)),
+            (0.0, Color::new(0.1, 0.1, 0.1)),
+        ];
+
+        for (intensity, expected) in cases {
+            let samples: Vec<LightSample> = light.samples(position)
+                .into_iter()
+                .map(|sample| LightSample { intensity: sample.intensity * intensity, ..sample })
+                .collect();
+
+            assert_eq!(expected, m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &samples));
+        }
+    }
+
+    #[test]
+    fn lighting_samples_the_area_light() {
+        let corner = Tuple::point(-0.5, -0.5, -5.0);
+        let v1 = Tuple::vector(1.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 1.0, 0.0);
+        let light = Light::Area(AreaLight::new(corner, v1, 2, v2, 2, Color::white()).with_jitter(false));
+        let m = Material {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let eye = Tuple::point(0.0, 0.0, -5.0);
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let cases = [
+            (Tuple::point(0.0, 0.0, -1.0), Color::new(0.9965, 0.9965, 0.9965)),
+            (Tuple::point(0.0, sqrt_two, -sqrt_two), Color::new(0.62318, 0.62318, 0.62318)),
+        ];
+
+        for (point, expected) in cases {
+            let eye_v = (eye - point).normalize();
+            let normal_v = Tuple::vector(point.x, point.y, point.z);
+
+            assert_eq!(expected, m.lighting_samples(&Sphere::new(), light, point, eye_v, normal_v, &light.samples(point)));
+        }
+    }
+
+    #[test]
+    fn lighting_with_an_attenuated_point_light() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye_v = Tuple::vector(0.0, 0.0, -1.0);
+        let normal_v = Tuple::vector(0.0, 0.0, -1.0);
+        let cases = [
+            (1.0, Color::new(1.9, 1.9, 1.9)),
+            (2.0, Color::new(0.55, 0.55, 0.55)),
+            (4.0, Color::new(0.2125, 0.2125, 0.2125)),
+        ];
+
+        for (distance, expected) in cases {
+            let light = Light::Point(PointLight::new(Tuple::point(0.0, 0.0, -distance), Color::white())
+                .with_attenuation(Attenuation::inverse_square()));
+
+            let result = m.lighting_samples(&Sphere::new(), light, position, eye_v, normal_v, &light.samples(position));
+
+            assert_eq!(expected, result);
+        }
+    }
+}
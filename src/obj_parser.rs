@@ -0,0 +1,448 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::Tuple;
+use crate::shapes::{Group, Shape, SmoothTriangle, Triangle};
+
+
+/// Why a line of an OBJ file did not contribute to the parsed result
+#[derive(Clone, Debug, PartialEq)]
+pub enum IgnoredReason {
+    /// The statement is not one the parser understands
+    Unsupported,
+    /// The statement is understood but its arguments are invalid
+    Malformed(String),
+}
+
+/// A line of an OBJ file that was skipped while parsing
+#[derive(Clone, Debug, PartialEq)]
+pub struct IgnoredLine {
+    pub line_number: usize,
+    pub content: String,
+    pub reason: IgnoredReason,
+}
+
+impl fmt::Display for IgnoredLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            IgnoredReason::Unsupported => {
+                write!(f, "line {}: unsupported statement `{}`", self.line_number, self.content)
+            }
+            IgnoredReason::Malformed(message) => {
+                write!(f, "line {}: {} in `{}`", self.line_number, message, self.content)
+            }
+        }
+    }
+}
+
+/// The triangles of an OBJ file, sorted into the groups named by its `g`
+/// statements. Faces that appear before any `g` statement end up in
+/// `default_group`.
+#[derive(Debug)]
+pub struct ObjParser {
+    pub vertices: Vec<Tuple>,
+    pub normals: Vec<Tuple>,
+    pub default_group: Vec<Shape>,
+    pub groups: Vec<(String, Vec<Shape>)>,
+    pub ignored: Vec<IgnoredLine>,
+}
+
+impl ObjParser {
+    pub fn parse(input: &str) -> Self {
+        let mut parser = ObjParser {
+            vertices: vec![],
+            normals: vec![],
+            default_group: vec![],
+            groups: vec![],
+            ignored: vec![],
+        };
+
+        let mut current_group: Option<usize> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            let line_number = i + 1;
+            let content = line.trim();
+            let mut tokens = content.split_whitespace();
+
+            let result = match tokens.next() {
+                // Blank lines and comments carry no data and are not reported
+                None => Ok(()),
+                Some(keyword) if keyword.starts_with('#') => Ok(()),
+                Some("v") => parse_tuple(tokens).map(|(x, y, z)| {
+                    parser.vertices.push(Tuple::point(x, y, z))
+                }),
+                Some("vn") => parse_tuple(tokens).map(|(x, y, z)| {
+                    parser.normals.push(Tuple::vector(x, y, z).normalize())
+                }),
+                Some("f") => parser.parse_face(tokens).map(|triangles| {
+                    match current_group {
+                        Some(index) => parser.groups[index].1.extend(triangles),
+                        None => parser.default_group.extend(triangles),
+                    }
+                }),
+                Some("g") => match tokens.next() {
+                    Some(name) => {
+                        current_group = Some(parser.group_index(name));
+                        Ok(())
+                    }
+                    None => Err(IgnoredReason::Malformed(String::from("missing group name"))),
+                },
+                Some(_) => Err(IgnoredReason::Unsupported),
+            };
+
+            if let Err(reason) = result {
+                parser.ignored.push(IgnoredLine {
+                    line_number,
+                    content: content.to_string(),
+                    reason,
+                });
+            }
+        }
+
+        parser
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let input = fs::read_to_string(path)?;
+
+        Ok(ObjParser::parse(&input))
+    }
+
+    pub fn group(&self, name: &str) -> Option<&[Shape]> {
+        self.groups
+            .iter()
+            .find(|(group_name, _)| group_name == name)
+            .map(|(_, shapes)| shapes.as_slice())
+    }
+
+    /// Returns every triangle in the file, ready to be added to a world
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let mut shapes = self.default_group.clone();
+
+        for (_, group) in &self.groups {
+            shapes.extend_from_slice(group);
+        }
+
+        shapes
+    }
+
+    /// Returns the file as a single group, with every named group nested
+    /// inside it as a child group of its own
+    pub fn to_group(&self) -> Group {
+        let mut group = Group::new().with_children(self.default_group.clone());
+
+        for (_, shapes) in &self.groups {
+            group.add_child(Shape::Group(Group::new().with_children(shapes.clone())));
+        }
+
+        group
+    }
+
+    fn group_index(&mut self, name: &str) -> usize {
+        match self.groups.iter().position(|(group_name, _)| group_name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push((name.to_string(), vec![]));
+                self.groups.len() - 1
+            }
+        }
+    }
+
+    /// Parses the vertices of a face and triangulates it as a fan around
+    /// its first vertex
+    fn parse_face<'a>(&self, tokens: impl Iterator<Item = &'a str>) -> Result<Vec<Shape>, IgnoredReason> {
+        let mut vertices = vec![];
+        let mut normals = vec![];
+
+        for token in tokens {
+            let (vertex, normal) = self.parse_face_vertex(token)?;
+            vertices.push(vertex);
+            normals.push(normal);
+        }
+
+        if vertices.len() < 3 {
+            return Err(IgnoredReason::Malformed(String::from("face needs at least three vertices")));
+        }
+
+        let mut triangles = vec![];
+
+        for i in 1..vertices.len() - 1 {
+            let triangle = match (normals[0], normals[i], normals[i + 1]) {
+                (Some(n1), Some(n2), Some(n3)) => Shape::SmoothTriangle(SmoothTriangle::new(
+                    vertices[0], vertices[i], vertices[i + 1], n1, n2, n3)),
+                _ => Shape::Triangle(Triangle::new(vertices[0], vertices[i], vertices[i + 1])),
+            };
+
+            triangles.push(triangle);
+        }
+
+        Ok(triangles)
+    }
+
+    /// Parses a `v`, `v/vt`, `v//vn` or `v/vt/vn` face vertex. Texture
+    /// coordinates are accepted but not used.
+    fn parse_face_vertex(&self, token: &str) -> Result<(Tuple, Option<Tuple>), IgnoredReason> {
+        let mut parts = token.split('/');
+
+        let vertex = match parts.next() {
+            Some(index) => lookup(&self.vertices, index, "vertex")?,
+            None => return Err(IgnoredReason::Malformed(String::from("empty face vertex"))),
+        };
+
+        let normal = match parts.nth(1) {
+            Some(index) if !index.is_empty() => Some(lookup(&self.normals, index, "normal")?),
+            _ => None,
+        };
+
+        Ok((vertex, normal))
+    }
+}
+
+fn parse_tuple<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<(f64, f64, f64), IgnoredReason> {
+    let mut next = || -> Result<f64, IgnoredReason> {
+        let token = tokens
+            .next()
+            .ok_or_else(|| IgnoredReason::Malformed(String::from("expected three coordinates")))?;
+
+        token
+            .parse::<f64>()
+            .map_err(|_| IgnoredReason::Malformed(format!("invalid number `{}`", token)))
+    };
+
+    Ok((next()?, next()?, next()?))
+}
+
+/// Looks up a 1-based OBJ index. Negative indices count back from the most
+/// recently defined element.
+fn lookup(elements: &[Tuple], index: &str, kind: &str) -> Result<Tuple, IgnoredReason> {
+    let parsed = index
+        .parse::<i64>()
+        .map_err(|_| IgnoredReason::Malformed(format!("invalid {} index `{}`", kind, index)))?;
+
+    let position = if parsed > 0 {
+        Some(parsed as usize - 1)
+    } else if parsed < 0 {
+        elements.len().checked_sub(parsed.unsigned_abs() as usize)
+    } else {
+        None
+    };
+
+    position
+        .and_then(|position| elements.get(position).copied())
+        .ok_or_else(|| IgnoredReason::Malformed(format!("{} index {} is out of range", kind, parsed)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::obj_parser::{IgnoredReason, ObjParser};
+    use crate::Tuple;
+    use crate::shapes::Shape;
+
+    #[test]
+    fn ignoring_unrecognized_lines() {
+        let gibberish = "There was a young lady named Bright
+who traveled much faster than light.
+She set out one day
+in a relative way,
+and came back the previous night.";
+
+        let parser = ObjParser::parse(gibberish);
+
+        assert_eq!(5, parser.ignored.len());
+        assert_eq!(1, parser.ignored[0].line_number);
+        assert_eq!(IgnoredReason::Unsupported, parser.ignored[0].reason);
+        assert!(parser.to_shapes().is_empty());
+    }
+
+    #[test]
+    fn vertex_records() {
+        let file = "v -1 1 0
+v -1.0000 0.5000 0.0000
+v 1 0 0
+v 1 1 0";
+
+        let parser = ObjParser::parse(file);
+
+        assert_eq!(Tuple::point(-1.0, 1.0, 0.0), parser.vertices[0]);
+        assert_eq!(Tuple::point(-1.0, 0.5, 0.0), parser.vertices[1]);
+        assert_eq!(Tuple::point(1.0, 0.0, 0.0), parser.vertices[2]);
+        assert_eq!(Tuple::point(1.0, 1.0, 0.0), parser.vertices[3]);
+    }
+
+    #[test]
+    fn parsing_triangle_faces() {
+        let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+f 1 3 4";
+
+        let parser = ObjParser::parse(file);
+
+        let (t1, t2) = match (&parser.default_group[0], &parser.default_group[1]) {
+            (Shape::Triangle(t1), Shape::Triangle(t2)) => (t1, t2),
+            _ => panic!("expected two triangles"),
+        };
+
+        assert_eq!(parser.vertices[0], t1.p1);
+        assert_eq!(parser.vertices[1], t1.p2);
+        assert_eq!(parser.vertices[2], t1.p3);
+        assert_eq!(parser.vertices[0], t2.p1);
+        assert_eq!(parser.vertices[2], t2.p2);
+        assert_eq!(parser.vertices[3], t2.p3);
+        assert!(parser.ignored.is_empty());
+    }
+
+    #[test]
+    fn triangulating_polygons() {
+        let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+
+f 1 2 3 4 5";
+
+        let parser = ObjParser::parse(file);
+
+        assert_eq!(3, parser.default_group.len());
+
+        let expected = [(0, 1, 2), (0, 2, 3), (0, 3, 4)];
+
+        for (shape, (a, b, c)) in parser.default_group.iter().zip(expected) {
+            let Shape::Triangle(t) = shape else { panic!("expected a triangle") };
+
+            assert_eq!(parser.vertices[a], t.p1);
+            assert_eq!(parser.vertices[b], t.p2);
+            assert_eq!(parser.vertices[c], t.p3);
+        }
+    }
+
+    #[test]
+    fn triangles_in_groups() {
+        let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4";
+
+        let parser = ObjParser::parse(file);
+
+        assert!(parser.default_group.is_empty());
+        assert_eq!(1, parser.group("FirstGroup").unwrap().len());
+        assert_eq!(1, parser.group("SecondGroup").unwrap().len());
+        assert_eq!(2, parser.to_shapes().len());
+    }
+
+    #[test]
+    fn converting_an_obj_file_to_a_group() {
+        let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 4
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4";
+
+        let parser = ObjParser::parse(file);
+        let g = parser.to_group();
+
+        assert_eq!(3, g.children().len());
+        assert!(matches!(g.children()[0], Shape::Triangle(_)));
+        assert!(matches!(g.children()[1], Shape::Group(_)));
+        assert!(matches!(g.children()[2], Shape::Group(_)));
+    }
+
+    #[test]
+    fn vertex_normal_records() {
+        let file = "vn 0 0 1
+vn 0.707 0 -0.707
+vn 1 2 3";
+
+        let parser = ObjParser::parse(file);
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+
+        assert_eq!(Tuple::vector(0.0, 0.0, 1.0), parser.normals[0]);
+        assert_eq!(Tuple::vector(sqrt_two, 0.0, -sqrt_two), parser.normals[1]);
+        assert_eq!(Tuple::vector(1.0, 2.0, 3.0).normalize(), parser.normals[2]);
+    }
+
+    #[test]
+    fn faces_with_normals() {
+        let file = "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+vn -1 0 0
+vn 1 0 0
+vn 0 1 0
+
+f 1//3 2//1 3//2
+f 1/0/3 2/102/1 3/14/2";
+
+        let parser = ObjParser::parse(file);
+
+        for shape in &parser.default_group {
+            let Shape::SmoothTriangle(t) = shape else { panic!("expected a smooth triangle") };
+
+            assert_eq!(parser.vertices[0], t.p1);
+            assert_eq!(parser.vertices[1], t.p2);
+            assert_eq!(parser.vertices[2], t.p3);
+            assert_eq!(parser.normals[2], t.n1);
+            assert_eq!(parser.normals[0], t.n2);
+            assert_eq!(parser.normals[1], t.n3);
+        }
+
+        assert_eq!(2, parser.default_group.len());
+    }
+
+    #[test]
+    fn negative_indices_count_back_from_the_last_vertex() {
+        let file = "v 0 1 0
+v -1 0 0
+v 1 0 0
+f -3 -2 -1";
+
+        let parser = ObjParser::parse(file);
+
+        let Shape::Triangle(t) = &parser.default_group[0] else { panic!("expected a triangle") };
+
+        assert_eq!(parser.vertices[0], t.p1);
+        assert_eq!(parser.vertices[2], t.p3);
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_with_line_numbers() {
+        let file = "v 0 1 0
+v -1 zero 0
+v 1 0 0
+f 1 2 3
+f 1 2
+g";
+
+        let parser = ObjParser::parse(file);
+
+        let line_numbers: Vec<usize> = parser.ignored.iter().map(|i| i.line_number).collect();
+
+        assert_eq!(vec![2, 4, 5, 6], line_numbers);
+        assert_eq!(
+            IgnoredReason::Malformed(String::from("invalid number `zero`")),
+            parser.ignored[0].reason);
+        assert_eq!(
+            IgnoredReason::Malformed(String::from("vertex index 3 is out of range")),
+            parser.ignored[1].reason);
+        assert_eq!("line 2: invalid number `zero` in `v -1 zero 0`", parser.ignored[0].to_string());
+        assert_eq!(2, parser.vertices.len());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+
+
+/// Averages the colors of two patterns at every point, such as two stripe
+/// patterns at right angles to make a plaid
+#[derive(Clone, Debug, PartialEq)]
+pub struct Blend {
+    pub a: Box<Pattern>,
+    pub b: Box<Pattern>,
+    pub transform: Matrix4,
+}
+
+impl Blend {
+    pub fn new(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Self {
+        Blend {
+            a: Box::new(a.into()),
+            b: Box::new(b.into()),
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Blend {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let a = self.a.color_at_pattern_point(point);
+        let b = self.b.color_at_pattern_point(point);
+
+        (a + b) * 0.5
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Blend {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Blend {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::blend::Blend;
+    use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+    use crate::patterns::Stripe;
+    use crate::{Color, rotation_y, Transform, Tuple};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn blend_averages_two_patterns() {
+        let horizontal = Stripe::new(Color::white(), Color::black());
+        let vertical = Stripe::new(Color::white(), Color::black()).rotate_y(PI / 2.0).transform();
+        let pattern = Blend::new(Pattern::Stripe(horizontal), Pattern::Stripe(vertical));
+
+        assert_eq!(Color::white(), pattern.local_color_at(Tuple::point(0.5, 0.0, -0.5)));
+        assert_eq!(Color::new(0.5, 0.5, 0.5), pattern.local_color_at(Tuple::point(1.5, 0.0, -0.5)));
+        assert_eq!(Color::black(), pattern.local_color_at(Tuple::point(1.5, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn blend_of_two_colors_is_their_average() {
+        let pattern = Blend::new(Color::new(1.0, 0.0, 0.0), Color::new(0.0, 0.0, 1.0))
+            .with_transform(rotation_y(PI / 4.0));
+
+        assert_eq!(Color::new(0.5, 0.0, 0.5), pattern.local_color_at(Tuple::point(3.0, 2.0, 1.0)));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+
+
+/// Unit cubes alternating between `a` and `b` in all three dimensions
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checker {
+    pub a: Box<Pattern>,
+    pub b: Box<Pattern>,
+    pub transform: Matrix4,
+}
+
+impl Checker {
+    /// Creates the pattern from two colors, two patterns, or one of each
+    pub fn new(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Self {
+        Checker {
+            a: Box::new(a.into()),
+            b: Box::new(b.into()),
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Checker {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let sum = point.x.floor() + point.y.floor() + point.z.floor();
+
+        if sum % 2.0 == 0.0 {
+            self.a.color_at_pattern_point(point)
+        } else {
+            self.b.color_at_pattern_point(point)
+        }
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Checker {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Checker {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::checker::Checker;
+    use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+    use crate::patterns::Stripe;
+    use crate::{Color, rotation_y, scaling, Tuple};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn checkers_repeat_in_every_dimension() {
+        let pattern = Checker::new(Color::white(), Color::black());
+        let cases = [
+            (Tuple::point(0.0, 0.0, 0.0), Color::white()),
+            (Tuple::point(0.99, 0.0, 0.0), Color::white()),
+            (Tuple::point(1.01, 0.0, 0.0), Color::black()),
+            (Tuple::point(0.0, 0.99, 0.0), Color::white()),
+            (Tuple::point(0.0, 1.01, 0.0), Color::black()),
+            (Tuple::point(0.0, 0.0, 0.99), Color::white()),
+            (Tuple::point(0.0, 0.0, 1.01), Color::black()),
+            (Tuple::point(-0.5, 0.0, -0.5), Color::white()),
+            (Tuple::point(-0.5, 0.0, 0.5), Color::black()),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, pattern.local_color_at(point));
+        }
+    }
+
+    #[test]
+    fn checkers_of_nested_stripes() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let along_x = Stripe::new(red, Color::white()).with_transform(scaling(0.25, 0.25, 0.25));
+        let along_z = Stripe::new(blue, Color::black())
+            .with_transform(rotation_y(PI / 2.0) * scaling(0.25, 0.25, 0.25));
+        let pattern = Checker::new(Pattern::Stripe(along_x), Pattern::Stripe(along_z));
+
+        // The first checker cell holds stripes a quarter unit wide along x
+        assert_eq!(red, pattern.local_color_at(Tuple::point(0.1, 0.0, 0.1)));
+        assert_eq!(Color::white(), pattern.local_color_at(Tuple::point(0.3, 0.0, 0.1)));
+
+        // Its neighbour holds stripes along z instead
+        assert_eq!(Color::black(), pattern.local_color_at(Tuple::point(1.1, 0.0, 0.1)));
+        assert_eq!(blue, pattern.local_color_at(Tuple::point(1.1, 0.0, 0.3)));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+
+
+/// Blends linearly from `a` to `b` across each unit of the x axis
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub a: Box<Pattern>,
+    pub b: Box<Pattern>,
+    pub transform: Matrix4,
+}
+
+impl Gradient {
+    /// Creates the pattern from two colors, two patterns, or one of each
+    pub fn new(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Self {
+        Gradient {
+            a: Box::new(a.into()),
+            b: Box::new(b.into()),
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Gradient {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let fraction = point.x - point.x.floor();
+
+        let a = self.a.color_at_pattern_point(point);
+        let b = self.b.color_at_pattern_point(point);
+
+        a + (b - a) * fraction
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Gradient {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Gradient {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::gradient::Gradient;
+    use crate::patterns::pattern_enum::ColorPattern;
+    use crate::{Color, Tuple};
+
+    #[test]
+    fn gradient_linearly_interpolates_between_colors() {
+        let pattern = Gradient::new(Color::white(), Color::black());
+        let cases = [
+            (0.0, Color::white()),
+            (0.25, Color::new(0.75, 0.75, 0.75)),
+            (0.5, Color::new(0.5, 0.5, 0.5)),
+            (0.75, Color::new(0.25, 0.25, 0.25)),
+        ];
+
+        for (x, expected) in cases {
+            assert_eq!(expected, pattern.local_color_at(Tuple::point(x, 0.0, 0.0)));
+        }
+    }
+}
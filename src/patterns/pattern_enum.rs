@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::blend::Blend;
+use crate::patterns::checker::Checker;
+use crate::patterns::gradient::Gradient;
+use crate::patterns::perturbed::Perturbed;
+use crate::patterns::ring::Ring;
+use crate::patterns::stripe::Stripe;
+use crate::patterns::texture_map::TextureMap;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+pub trait ColorPattern: Debug {
+    /// Computes the color at a point given in the pattern's own space
+    fn local_color_at(&self, point: Tuple) -> Color;
+
+    fn local_transform(&self) -> Matrix4;
+
+    /// Computes the color at a point given in the space of the pattern's
+    /// parent, which is either the object or a pattern this one is nested in
+    fn color_at_pattern_point(&self, point: Tuple) -> Color {
+        let pattern_point = match self.local_transform().inverse() {
+            Some(inverse) => inverse * point,
+            None => point
+        };
+
+        self.local_color_at(pattern_point)
+    }
+
+    /// Computes the color at a world space point on `object`. The point is
+    /// converted to the object's space and then to the pattern's, so the
+    /// pattern moves along with the object it is applied to.
+    fn color_at_object(&self, object: &dyn RayInteractable, world_point: Tuple) -> Color {
+        self.color_at_pattern_point(object.world_to_object(world_point))
+    }
+}
+
+/// A plain color is a pattern that is the same everywhere
+impl ColorPattern for Color {
+    fn local_color_at(&self, _point: Tuple) -> Color {
+        *self
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        Matrix4::identity_matrix()
+    }
+
+    fn color_at_pattern_point(&self, _point: Tuple) -> Color {
+        *self
+    }
+}
+
+
+/// Patterns can be nested: the colors of stripes, gradients, rings,
+/// checkers and blends are patterns themselves, with `Solid` at the leaves
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+    Solid(Color),
+    Stripe(Stripe),
+    Gradient(Gradient),
+    Ring(Ring),
+    Checker(Checker),
+    Blend(Blend),
+    Perturbed(Perturbed),
+    TextureMap(TextureMap),
+}
+
+impl From<Color> for Pattern {
+    fn from(color: Color) -> Self {
+        Pattern::Solid(color)
+    }
+}
+
+impl Pattern {
+    fn as_color_pattern(&self) -> &dyn ColorPattern {
+        match self {
+            Pattern::Solid(color) => color,
+            Pattern::Stripe(stripe) => stripe,
+            Pattern::Gradient(gradient) => gradient,
+            Pattern::Ring(ring) => ring,
+            Pattern::Checker(checker) => checker,
+            Pattern::Blend(blend) => blend,
+            Pattern::Perturbed(perturbed) => perturbed,
+            Pattern::TextureMap(texture_map) => texture_map,
+        }
+    }
+}
+
+impl ColorPattern for Pattern {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        self.as_color_pattern().local_color_at(point)
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.as_color_pattern().local_transform()
+    }
+
+    fn color_at_pattern_point(&self, point: Tuple) -> Color {
+        self.as_color_pattern().color_at_pattern_point(point)
+    }
+}
+
+impl Transform for Pattern {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        match self {
+            Pattern::Solid(color) => Pattern::Solid(color),
+            Pattern::Stripe(stripe) => Pattern::Stripe(stripe.transform(transformation)),
+            Pattern::Gradient(gradient) => Pattern::Gradient(gradient.transform(transformation)),
+            Pattern::Ring(ring) => Pattern::Ring(ring.transform(transformation)),
+            Pattern::Checker(checker) => Pattern::Checker(checker.transform(transformation)),
+            Pattern::Blend(blend) => Pattern::Blend(blend.transform(transformation)),
+            Pattern::Perturbed(perturbed) => Pattern::Perturbed(perturbed.transform(transformation)),
+            Pattern::TextureMap(texture_map) => Pattern::TextureMap(texture_map.transform(transformation)),
+        }
+    }
+}
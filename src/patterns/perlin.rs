@@ -0,0 +1,142 @@
+//! Ken Perlin's improved gradient noise in three dimensions
+
+
+/// The permutation table, repeated once so that lookups never have to wrap
+const PERMUTATION: [u8; 512] = build_permutation();
+
+/// Shuffles 0 to 255 with a fixed seed, so noise is the same on every run
+const fn build_permutation() -> [u8; 512] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        table[i] = i as u8;
+        i += 1;
+    }
+
+    let mut seed: u32 = 0x2545_f491;
+    let mut i = 255;
+
+    while i > 0 {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        let j = (seed >> 8) as usize % (i + 1);
+        let swap = table[i];
+        table[i] = table[j];
+        table[j] = swap;
+        i -= 1;
+    }
+
+    let mut doubled = [0u8; 512];
+    let mut i = 0;
+
+    while i < 512 {
+        doubled[i] = table[i % 256];
+        i += 1;
+    }
+
+    doubled
+}
+
+/// Smooths the fractional position in a lattice cell so the noise has no
+/// visible creases at cell boundaries
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Dots the offset from a lattice corner with one of twelve gradient
+/// directions picked by the corner's hash
+fn grad(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// Computes smooth noise at a point, roughly between -1 and 1. The noise is
+/// zero at every point with integer coordinates.
+pub fn noise(x: f64, y: f64, z: f64) -> f64 {
+    let p = &PERMUTATION;
+
+    // The lattice cell containing the point
+    let xi = (x.floor() as i64 & 255) as usize;
+    let yi = (y.floor() as i64 & 255) as usize;
+    let zi = (z.floor() as i64 & 255) as usize;
+
+    // The point relative to the cell
+    let x = x - x.floor();
+    let y = y - y.floor();
+    let z = z - z.floor();
+
+    let u = fade(x);
+    let v = fade(y);
+    let w = fade(z);
+
+    // Hashes of the eight cell corners
+    let a = p[xi] as usize + yi;
+    let aa = p[a] as usize + zi;
+    let ab = p[a + 1] as usize + zi;
+    let b = p[xi + 1] as usize + yi;
+    let ba = p[b] as usize + zi;
+    let bb = p[b + 1] as usize + zi;
+
+    lerp(w,
+         lerp(v,
+              lerp(u, grad(p[aa], x, y, z), grad(p[ba], x - 1.0, y, z)),
+              lerp(u, grad(p[ab], x, y - 1.0, z), grad(p[bb], x - 1.0, y - 1.0, z))),
+         lerp(v,
+              lerp(u, grad(p[aa + 1], x, y, z - 1.0), grad(p[ba + 1], x - 1.0, y, z - 1.0)),
+              lerp(u, grad(p[ab + 1], x, y - 1.0, z - 1.0), grad(p[bb + 1], x - 1.0, y - 1.0, z - 1.0))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::perlin::{noise, PERMUTATION};
+
+    #[test]
+    fn permutation_table_holds_every_byte_once() {
+        let mut seen = [false; 256];
+
+        for value in &PERMUTATION[..256] {
+            assert!(!seen[*value as usize]);
+            seen[*value as usize] = true;
+        }
+
+        assert_eq!(PERMUTATION[..256], PERMUTATION[256..]);
+    }
+
+    #[test]
+    fn noise_is_zero_on_the_lattice() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, 2.0, 3.0), (-4.0, 7.0, -1.0)] {
+            assert_eq!(0.0, noise(x, y, z));
+        }
+    }
+
+    #[test]
+    fn noise_varies_smoothly_and_stays_in_range() {
+        let mut previous = noise(0.0, 0.5, 0.5);
+        let mut varies = false;
+
+        for i in 1..1000 {
+            let value = noise(i as f64 * 0.01, 0.5, 0.5);
+
+            assert!((-1.0..=1.0).contains(&value));
+            assert!((value - previous).abs() < 0.1);
+
+            varies |= value != previous;
+            previous = value;
+        }
+
+        assert!(varies);
+    }
+}
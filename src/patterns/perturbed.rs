@@ -0,0 +1,92 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+use crate::patterns::perlin::noise;
+
+
+/// Wraps a pattern and jitters every lookup point with Perlin noise, which
+/// turns straight stripes and rings into marble or wood grain. `scale` is the
+/// largest distance a point is moved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Perturbed {
+    pub pattern: Box<Pattern>,
+    pub scale: f64,
+    pub transform: Matrix4,
+}
+
+impl Perturbed {
+    pub fn new(pattern: impl Into<Pattern>, scale: f64) -> Self {
+        Perturbed {
+            pattern: Box::new(pattern.into()),
+            scale,
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Perturbed {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let (x, y, z) = (point.x, point.y, point.z);
+
+        // Offset the noise lookups so each axis is jittered independently
+        let jittered = Tuple::point(
+            x + noise(x, y, z) * self.scale,
+            y + noise(x, y, z + 1.5) * self.scale,
+            z + noise(x, y, z + 3.5) * self.scale);
+
+        self.pattern.color_at_pattern_point(jittered)
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Perturbed {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Perturbed {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::perturbed::Perturbed;
+    use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+    use crate::patterns::Stripe;
+    use crate::{Color, Tuple};
+
+    #[test]
+    fn perturbing_by_zero_leaves_the_pattern_unchanged() {
+        let stripes = Pattern::Stripe(Stripe::new(Color::white(), Color::black()));
+        let pattern = Perturbed::new(stripes.clone(), 0.0);
+
+        for x in [0.2, 0.9, 1.1, 1.9] {
+            let point = Tuple::point(x, 0.3, 0.7);
+
+            assert_eq!(stripes.local_color_at(point), pattern.local_color_at(point));
+        }
+    }
+
+    #[test]
+    fn perturbing_moves_stripe_boundaries() {
+        let pattern = Perturbed::new(Pattern::Stripe(Stripe::new(Color::white(), Color::black())), 0.5);
+
+        // Sample along a line crossing the boundary at x = 1 at several
+        // heights. The noise moves the boundary by a different amount at
+        // each height, so the colors just before it don't all agree.
+        let colors: Vec<Color> = (0..20)
+            .map(|i| pattern.local_color_at(Tuple::point(0.95, i as f64 * 0.37, 0.43)))
+            .collect();
+
+        assert!(colors.iter().any(|c| *c == Color::white()));
+        assert!(colors.iter().any(|c| *c == Color::black()));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+
+
+/// Concentric rings around the y axis, alternating between `a` and `b`
+/// every unit of distance
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ring {
+    pub a: Box<Pattern>,
+    pub b: Box<Pattern>,
+    pub transform: Matrix4,
+}
+
+impl Ring {
+    /// Creates the pattern from two colors, two patterns, or one of each
+    pub fn new(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Self {
+        Ring {
+            a: Box::new(a.into()),
+            b: Box::new(b.into()),
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Ring {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let distance = (point.x * point.x + point.z * point.z).sqrt();
+
+        if distance.floor() % 2.0 == 0.0 {
+            self.a.color_at_pattern_point(point)
+        } else {
+            self.b.color_at_pattern_point(point)
+        }
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Ring {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Ring {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::ring::Ring;
+    use crate::patterns::pattern_enum::ColorPattern;
+    use crate::{Color, Tuple};
+
+    #[test]
+    fn ring_extends_in_both_x_and_z() {
+        let pattern = Ring::new(Color::white(), Color::black());
+        let cases = [
+            (Tuple::point(0.0, 0.0, 0.0), Color::white()),
+            (Tuple::point(1.0, 0.0, 0.0), Color::black()),
+            (Tuple::point(0.0, 0.0, 1.0), Color::black()),
+            (Tuple::point(0.708, 0.0, 0.708), Color::black()),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, pattern.local_color_at(point));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+
+
+/// Alternates between `a` and `b` every unit along the x axis
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stripe {
+    pub a: Box<Pattern>,
+    pub b: Box<Pattern>,
+    pub transform: Matrix4,
+}
+
+impl Stripe {
+    /// Creates the pattern from two colors, two patterns, or one of each
+    pub fn new(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Self {
+        Stripe {
+            a: Box::new(a.into()),
+            b: Box::new(b.into()),
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for Stripe {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        if point.x.floor() % 2.0 == 0.0 {
+            self.a.color_at_pattern_point(point)
+        } else {
+            self.b.color_at_pattern_point(point)
+        }
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for Stripe {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Stripe {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::stripe::Stripe;
+    use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+    use crate::shapes::Sphere;
+    use crate::{Color, scaling, translation, Tuple};
+
+    #[test]
+    fn creating_a_stripe_pattern() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+
+        assert_eq!(Pattern::from(Color::white()), *pattern.a);
+        assert_eq!(Pattern::from(Color::black()), *pattern.b);
+    }
+
+    #[test]
+    fn stripe_pattern_is_constant_in_y_and_z() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+
+        for y in [0.0, 1.0, 2.0] {
+            assert_eq!(Color::white(), pattern.local_color_at(Tuple::point(0.0, y, 0.0)));
+        }
+
+        for z in [0.0, 1.0, 2.0] {
+            assert_eq!(Color::white(), pattern.local_color_at(Tuple::point(0.0, 0.0, z)));
+        }
+    }
+
+    #[test]
+    fn stripe_pattern_alternates_in_x() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+        let cases = [
+            (0.0, Color::white()),
+            (0.9, Color::white()),
+            (1.0, Color::black()),
+            (-0.1, Color::black()),
+            (-1.0, Color::black()),
+            (-1.1, Color::white()),
+        ];
+
+        for (x, expected) in cases {
+            assert_eq!(expected, pattern.local_color_at(Tuple::point(x, 0.0, 0.0)));
+        }
+    }
+
+    #[test]
+    fn stripes_with_an_object_transformation() {
+        let object = Sphere::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let pattern = Stripe::new(Color::white(), Color::black());
+
+        let c = pattern.color_at_object(&object, Tuple::point(1.5, 0.0, 0.0));
+
+        assert_eq!(Color::white(), c);
+    }
+
+    #[test]
+    fn stripes_with_a_pattern_transformation() {
+        let object = Sphere::new();
+        let pattern = Stripe::new(Color::white(), Color::black()).with_transform(scaling(2.0, 2.0, 2.0));
+
+        let c = pattern.color_at_object(&object, Tuple::point(1.5, 0.0, 0.0));
+
+        assert_eq!(Color::white(), c);
+    }
+
+    #[test]
+    fn stripes_with_both_an_object_and_a_pattern_transformation() {
+        let object = Sphere::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let pattern = Stripe::new(Color::white(), Color::black()).with_transform(translation(0.5, 0.0, 0.0));
+
+        let c = pattern.color_at_object(&object, Tuple::point(2.5, 0.0, 0.0));
+
+        assert_eq!(Color::white(), c);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Color, Matrix4, Transform, Tuple};
+use crate::patterns::pattern_enum::ColorPattern;
+use crate::patterns::uv::{UvMapping, UvPattern};
+
+
+/// Wraps a 2D pattern around a shape by mapping each point to texture
+/// coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextureMap {
+    pub uv_pattern: UvPattern,
+    pub mapping: UvMapping,
+    pub transform: Matrix4,
+}
+
+impl TextureMap {
+    pub fn new(uv_pattern: UvPattern, mapping: UvMapping) -> Self {
+        TextureMap {
+            uv_pattern,
+            mapping,
+            transform: Matrix4::identity_matrix(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl ColorPattern for TextureMap {
+    fn local_color_at(&self, point: Tuple) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.color_at(u, v)
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+}
+
+impl Transform for TextureMap {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        TextureMap {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::patterns::pattern_enum::{ColorPattern, Pattern};
+    use crate::patterns::texture_map::TextureMap;
+    use crate::patterns::uv::{UvMapping, UvPattern};
+    use crate::shapes::Sphere;
+    use crate::{Color, rotation_y, Transform, Tuple};
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn using_a_texture_map_pattern_with_a_spherical_map() {
+        let checkers = UvPattern::checkers(16.0, 8.0, Color::black(), Color::white());
+        let pattern = TextureMap::new(checkers, UvMapping::Spherical);
+        let cases = [
+            (Tuple::point(0.4315, 0.4670, 0.7719), Color::white()),
+            (Tuple::point(-0.9654, 0.2552, -0.0534), Color::black()),
+            (Tuple::point(0.1039, 0.7090, 0.6975), Color::white()),
+            (Tuple::point(-0.4986, -0.7856, -0.3663), Color::black()),
+            (Tuple::point(-0.0317, -0.9395, 0.3411), Color::black()),
+            (Tuple::point(0.4809, -0.7721, 0.4154), Color::black()),
+            (Tuple::point(0.0285, -0.9612, -0.2745), Color::black()),
+            (Tuple::point(-0.5734, -0.2162, -0.7903), Color::white()),
+            (Tuple::point(0.7688, -0.1470, 0.6223), Color::black()),
+            (Tuple::point(-0.7652, 0.2175, 0.6060), Color::black()),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, pattern.local_color_at(point));
+        }
+    }
+
+    #[test]
+    fn using_a_texture_map_pattern_with_a_planar_map() {
+        let checkers = UvPattern::checkers(2.0, 2.0, Color::black(), Color::white());
+        let pattern = TextureMap::new(checkers, UvMapping::Planar);
+
+        assert_eq!(Color::black(), pattern.local_color_at(Tuple::point(0.25, 0.0, 0.25)));
+        assert_eq!(Color::white(), pattern.local_color_at(Tuple::point(0.75, 0.0, 0.25)));
+        assert_eq!(Color::black(), pattern.local_color_at(Tuple::point(1.25, 5.0, 1.25)));
+    }
+
+    #[test]
+    fn using_a_texture_map_pattern_with_a_cube_map() {
+        let main = Color::white();
+        let corner = Color::new(1.0, 0.0, 0.0);
+        let align_check = UvPattern::align_check(main, corner, main, main, main);
+        let pattern = TextureMap::new(align_check, UvMapping::Cube);
+
+        // The upper left corner of every face
+        assert_eq!(corner, pattern.local_color_at(Tuple::point(-0.9, 0.9, 1.0)));
+        assert_eq!(corner, pattern.local_color_at(Tuple::point(-1.0, 0.9, -0.9)));
+        assert_eq!(corner, pattern.local_color_at(Tuple::point(-0.9, 1.0, -0.9)));
+        assert_eq!(main, pattern.local_color_at(Tuple::point(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn a_texture_map_follows_the_sphere_it_is_applied_to() {
+        let checkers = UvPattern::checkers(2.0, 1.0, Color::black(), Color::white());
+        let pattern = Pattern::TextureMap(TextureMap::new(checkers, UvMapping::Spherical));
+        let sphere = Sphere::new().transform(&rotation_y(PI));
+
+        // Turning the sphere half way around swaps the two halves of the texture
+        let front = Tuple::point(0.0, 0.0, -1.0);
+        let object_front = sphere.world_to_object(front);
+
+        assert_eq!(Color::white(), pattern.color_at_object(&sphere, front));
+        assert_eq!(Color::black(), pattern.local_color_at(front));
+        assert_eq!(Color::white(), pattern.local_color_at(object_front));
+    }
+}
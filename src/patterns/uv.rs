@@ -0,0 +1,340 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+use crate::{Canvas, Color, Tuple};
+
+
+/// Converts an object space point on a shape's surface into 2D texture
+/// coordinates `u` and `v`, both between 0 and 1
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UvMapping {
+    /// Wraps the texture around a unit sphere, with `u` going around the
+    /// y axis and `v` from the south to the north pole
+    Spherical,
+    /// Tiles the texture across the xz plane once per unit
+    Planar,
+    /// Wraps the texture around a unit cylinder once, repeating every unit
+    /// of height
+    Cylindrical,
+    /// Puts the whole texture on each face of a unit cube
+    Cube,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: Tuple) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => planar_map(point),
+            UvMapping::Cylindrical => cylindrical_map(point),
+            UvMapping::Cube => CubeFace::from_point(point).map(point),
+        }
+    }
+}
+
+pub fn spherical_map(point: Tuple) -> (f64, f64) {
+    // The azimuthal angle around the y axis, from -pi to pi
+    let theta = point.x.atan2(point.z);
+
+    let radius = Tuple::vector(point.x, point.y, point.z).magnitude();
+
+    // The polar angle from the north pole, from 0 to pi
+    let phi = (point.y / radius).acos();
+
+    // Flip u so it increases counterclockwise when viewed from above
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+
+    // Flip v so 0 is at the south pole
+    let v = 1.0 - phi / PI;
+
+    (u, v)
+}
+
+pub fn planar_map(point: Tuple) -> (f64, f64) {
+    (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+}
+
+pub fn cylindrical_map(point: Tuple) -> (f64, f64) {
+    let theta = point.x.atan2(point.z);
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+
+    (u, point.y.rem_euclid(1.0))
+}
+
+/// A face of the unit cube, named as seen from the front along +z
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Front,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl CubeFace {
+    /// The face a point on the unit cube lies on, from the axis with the
+    /// largest component
+    pub fn from_point(point: Tuple) -> CubeFace {
+        let coord = point.x.abs().max(point.y.abs()).max(point.z.abs());
+
+        if coord == point.x {
+            CubeFace::Right
+        } else if coord == -point.x {
+            CubeFace::Left
+        } else if coord == point.y {
+            CubeFace::Up
+        } else if coord == -point.y {
+            CubeFace::Down
+        } else if coord == point.z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+
+    /// Maps a point on this face to `u` and `v`, oriented as if the face
+    /// were unfolded next to the front face
+    pub fn map(&self, point: Tuple) -> (f64, f64) {
+        let (u, v) = match self {
+            CubeFace::Front => (point.x + 1.0, point.y + 1.0),
+            CubeFace::Back => (1.0 - point.x, point.y + 1.0),
+            CubeFace::Left => (point.z + 1.0, point.y + 1.0),
+            CubeFace::Right => (1.0 - point.z, point.y + 1.0),
+            CubeFace::Up => (point.x + 1.0, 1.0 - point.z),
+            CubeFace::Down => (point.x + 1.0, point.z + 1.0),
+        };
+
+        (u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0)
+    }
+}
+
+/// A 2D pattern looked up by texture coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub enum UvPattern {
+    /// `width` by `height` squares alternating between `a` and `b`
+    Checkers { width: f64, height: f64, a: Color, b: Color },
+    /// A `main` color with a differently colored square in each corner,
+    /// which shows how a texture is oriented on a surface
+    AlignCheck { main: Color, upper_left: Color, upper_right: Color, bottom_left: Color, bottom_right: Color },
+    /// An image stretched over the whole texture, with `v` going up from
+    /// the bottom row. The canvas is shared so cloning the pattern is cheap.
+    Image(Arc<Canvas>),
+}
+
+impl UvPattern {
+    pub fn checkers(width: f64, height: f64, a: Color, b: Color) -> Self {
+        UvPattern::Checkers { width, height, a, b }
+    }
+
+    pub fn align_check(main: Color, upper_left: Color, upper_right: Color, bottom_left: Color, bottom_right: Color) -> Self {
+        UvPattern::AlignCheck { main, upper_left, upper_right, bottom_left, bottom_right }
+    }
+
+    pub fn image(canvas: Canvas) -> Self {
+        UvPattern::Image(Arc::new(canvas))
+    }
+
+    pub fn color_at(&self, u: f64, v: f64) -> Color {
+        match self {
+            UvPattern::Checkers { width, height, a, b } => {
+                let u2 = (u * width).floor();
+                let v2 = (v * height).floor();
+
+                if (u2 + v2) % 2.0 == 0.0 {
+                    *a
+                } else {
+                    *b
+                }
+            }
+            UvPattern::AlignCheck { main, upper_left, upper_right, bottom_left, bottom_right } => {
+                if v > 0.8 {
+                    if u < 0.2 {
+                        return *upper_left;
+                    }
+                    if u > 0.8 {
+                        return *upper_right;
+                    }
+                } else if v < 0.2 {
+                    if u < 0.2 {
+                        return *bottom_left;
+                    }
+                    if u > 0.8 {
+                        return *bottom_right;
+                    }
+                }
+
+                *main
+            }
+            UvPattern::Image(canvas) => {
+                let x = u * (canvas.width as f64 - 1.0);
+                let y = (1.0 - v) * (canvas.height as f64 - 1.0);
+
+                canvas.sample(x, y)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::uv::{CubeFace, cylindrical_map, planar_map, spherical_map, UvPattern};
+    use crate::comparison::ApproxEq;
+    use crate::{Canvas, Color, Tuple};
+
+    fn assert_uv(expected: (f64, f64), actual: (f64, f64)) {
+        assert!(expected.0.approx_eq_low_precision(actual.0), "u: {} != {}", expected.0, actual.0);
+        assert!(expected.1.approx_eq_low_precision(actual.1), "v: {} != {}", expected.1, actual.1);
+    }
+
+    #[test]
+    fn checker_pattern_in_2d() {
+        let checkers = UvPattern::checkers(2.0, 2.0, Color::black(), Color::white());
+        let cases = [
+            (0.0, 0.0, Color::black()),
+            (0.5, 0.0, Color::white()),
+            (0.0, 0.5, Color::white()),
+            (0.5, 0.5, Color::black()),
+            (1.0, 1.0, Color::black()),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, checkers.color_at(u, v));
+        }
+    }
+
+    #[test]
+    fn using_a_spherical_mapping_on_a_3d_point() {
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let cases = [
+            (Tuple::point(0.0, 0.0, -1.0), (0.0, 0.5)),
+            (Tuple::point(1.0, 0.0, 0.0), (0.25, 0.5)),
+            (Tuple::point(0.0, 0.0, 1.0), (0.5, 0.5)),
+            (Tuple::point(-1.0, 0.0, 0.0), (0.75, 0.5)),
+            (Tuple::point(0.0, 1.0, 0.0), (0.5, 1.0)),
+            (Tuple::point(0.0, -1.0, 0.0), (0.5, 0.0)),
+            (Tuple::point(sqrt_two, sqrt_two, 0.0), (0.25, 0.75)),
+        ];
+
+        for (point, expected) in cases {
+            assert_uv(expected, spherical_map(point));
+        }
+    }
+
+    #[test]
+    fn using_a_planar_mapping_on_a_3d_point() {
+        let cases = [
+            (Tuple::point(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::point(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (Tuple::point(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (Tuple::point(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::point(0.25, 0.0, -1.75), (0.25, 0.25)),
+            (Tuple::point(1.0, 0.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(0.0, 0.0, 0.0), (0.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_uv(expected, planar_map(point));
+        }
+    }
+
+    #[test]
+    fn using_a_cylindrical_mapping_on_a_3d_point() {
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let cases = [
+            (Tuple::point(0.0, 0.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (Tuple::point(0.0, 1.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(sqrt_two, 0.5, -sqrt_two), (0.125, 0.5)),
+            (Tuple::point(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (Tuple::point(sqrt_two, 0.5, sqrt_two), (0.375, 0.5)),
+            (Tuple::point(0.0, -0.25, 1.0), (0.5, 0.75)),
+            (Tuple::point(-sqrt_two, 0.5, sqrt_two), (0.625, 0.5)),
+            (Tuple::point(-1.0, 1.25, 0.0), (0.75, 0.25)),
+        ];
+
+        for (point, expected) in cases {
+            assert_uv(expected, cylindrical_map(point));
+        }
+    }
+
+    #[test]
+    fn layout_of_the_align_check_pattern() {
+        let main = Color::white();
+        let upper_left = Color::new(1.0, 0.0, 0.0);
+        let upper_right = Color::new(1.0, 1.0, 0.0);
+        let bottom_left = Color::new(0.0, 1.0, 0.0);
+        let bottom_right = Color::new(0.0, 1.0, 1.0);
+        let pattern = UvPattern::align_check(main, upper_left, upper_right, bottom_left, bottom_right);
+        let cases = [
+            (0.5, 0.5, main),
+            (0.1, 0.9, upper_left),
+            (0.9, 0.9, upper_right),
+            (0.1, 0.1, bottom_left),
+            (0.9, 0.1, bottom_right),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, pattern.color_at(u, v));
+        }
+    }
+
+    #[test]
+    fn sampling_an_image_pattern_at_uv_coordinates() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(0, 0, Color::red());
+        canvas.write_pixel(2, 0, Color::white());
+        canvas.write_pixel(2, 1, Color::new(0.0, 0.0, 1.0));
+        let pattern = UvPattern::image(canvas);
+        let cases = [
+            (0.0, 1.0, Color::red()),
+            (1.0, 1.0, Color::white()),
+            (0.0, 0.0, Color::black()),
+            (1.0, 0.0, Color::new(0.0, 0.0, 1.0)),
+            (0.25, 1.0, Color::new(0.5, 0.0, 0.0)),
+            (1.0, 0.5, Color::new(0.5, 0.5, 1.0)),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, pattern.color_at(u, v));
+        }
+    }
+
+    #[test]
+    fn identifying_the_face_of_a_cube_from_a_point() {
+        let cases = [
+            (Tuple::point(-1.0, 0.5, -0.25), CubeFace::Left),
+            (Tuple::point(1.1, -0.75, 0.8), CubeFace::Right),
+            (Tuple::point(0.1, 0.6, 0.9), CubeFace::Front),
+            (Tuple::point(-0.7, 0.0, -2.0), CubeFace::Back),
+            (Tuple::point(0.5, 1.0, 0.9), CubeFace::Up),
+            (Tuple::point(-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, CubeFace::from_point(point));
+        }
+    }
+
+    #[test]
+    fn uv_mapping_the_faces_of_a_cube() {
+        let cases = [
+            (CubeFace::Front, Tuple::point(-0.5, 0.5, 1.0), (0.25, 0.75)),
+            (CubeFace::Front, Tuple::point(0.5, -0.5, 1.0), (0.75, 0.25)),
+            (CubeFace::Back, Tuple::point(0.5, 0.5, -1.0), (0.25, 0.75)),
+            (CubeFace::Back, Tuple::point(-0.5, -0.5, -1.0), (0.75, 0.25)),
+            (CubeFace::Left, Tuple::point(-1.0, 0.5, -0.5), (0.25, 0.75)),
+            (CubeFace::Left, Tuple::point(-1.0, -0.5, 0.5), (0.75, 0.25)),
+            (CubeFace::Right, Tuple::point(1.0, 0.5, 0.5), (0.25, 0.75)),
+            (CubeFace::Right, Tuple::point(1.0, -0.5, -0.5), (0.75, 0.25)),
+            (CubeFace::Up, Tuple::point(-0.5, 1.0, -0.5), (0.25, 0.75)),
+            (CubeFace::Up, Tuple::point(0.5, 1.0, 0.5), (0.75, 0.25)),
+            (CubeFace::Down, Tuple::point(-0.5, -1.0, 0.5), (0.25, 0.75)),
+            (CubeFace::Down, Tuple::point(0.5, -1.0, -0.5), (0.75, 0.25)),
+        ];
+
+        for (face, point, expected) in cases {
+            assert_uv(expected, face.map(point));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+
+thread_local! {
+    // Every thread gets its own randomly seeded state, so render threads
+    // don't repeat each other's sequences
+    static STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+/// A fast, non-cryptographic random number in `[0, 1)` from a thread local
+/// xorshift64* generator, used for sampling
+pub(crate) fn random_f64() -> f64 {
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+
+        // The top 53 bits fill the mantissa of a double exactly
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::random_f64;
+
+    #[test]
+    fn random_numbers_are_between_zero_and_one() {
+        let numbers: Vec<f64> = (0..1000).map(|_| random_f64()).collect();
+
+        assert!(numbers.iter().all(|n| (0.0..1.0).contains(n)));
+        assert!(numbers.iter().any(|n| *n < 0.5));
+        assert!(numbers.iter().any(|n| *n >= 0.5));
+    }
+}
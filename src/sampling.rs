@@ -0,0 +1,230 @@
+use crate::Color;
+use crate::random::random_f64;
+
+
+/// Where the samples for a pixel are placed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SampleStrategy {
+    /// Evenly spaced in a square grid, which can alias on regular detail
+    Grid,
+    /// One random sample in each cell of a square grid, which spreads
+    /// samples evenly while turning aliasing into noise
+    Jittered,
+    /// Anywhere, independently of each other
+    Random,
+}
+
+impl SampleStrategy {
+    /// How many samples are actually taken when `samples` are asked for.
+    /// Grid and jittered sampling round the count down to a square number.
+    pub fn sample_count(&self, samples: usize) -> usize {
+        match self {
+            SampleStrategy::Random => samples.max(1),
+            _ => {
+                let side = (samples as f64).sqrt().floor().max(1.0) as usize;
+                side * side
+            }
+        }
+    }
+
+    /// `sample_count(samples)` positions in the unit square
+    pub fn offsets(&self, samples: usize) -> Vec<(f64, f64)> {
+        let samples = self.sample_count(samples);
+        if let SampleStrategy::Random = self {
+            return (0..samples).map(|_| (random_f64(), random_f64())).collect();
+        }
+
+        let side = (samples as f64).sqrt().round() as usize;
+        let mut offsets = Vec::with_capacity(side * side);
+
+        for row in 0..side {
+            for column in 0..side {
+                let (dx, dy) = match self {
+                    SampleStrategy::Jittered => (random_f64(), random_f64()),
+                    _ => (0.5, 0.5),
+                };
+
+                offsets.push(((column as f64 + dx) / side as f64, (row as f64 + dy) / side as f64));
+            }
+        }
+
+        offsets
+    }
+}
+
+/// Takes more samples only where the image has detail: after a first pass,
+/// pixels that differ from a neighbor by more than `threshold` in any
+/// channel are sampled again, up to `max_samples` in total
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdaptiveSampling {
+    pub threshold: Color,
+    pub max_samples: usize,
+}
+
+impl AdaptiveSampling {
+    pub fn new(threshold: Color, max_samples: usize) -> Self {
+        AdaptiveSampling { threshold, max_samples }
+    }
+
+    /// Whether two neighboring pixels differ enough to need more samples
+    pub fn exceeds_threshold(&self, a: Color, b: Color) -> bool {
+        (a.r - b.r).abs() > self.threshold.r
+            || (a.g - b.g).abs() > self.threshold.g
+            || (a.b - b.b).abs() > self.threshold.b
+    }
+}
+
+/// Weights samples by their distance from the pixel center, in pixels, when
+/// combining them into the pixel's color. Wider filters blur more but
+/// alias less.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// Every sample within half a pixel counts the same
+    Box,
+    /// Falls off linearly to nothing one pixel away
+    Tent,
+    /// A bell curve cut off one and a half pixels away
+    Gaussian,
+    /// The Mitchell-Netravali filter with B = C = 1/3, which stays sharp by
+    /// giving samples further away slightly negative weights
+    Mitchell,
+}
+
+impl Filter {
+    /// How far from the pixel center, in pixels, samples are taken
+    pub fn radius(&self) -> f64 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian => 1.5,
+            Filter::Mitchell => 2.0,
+        }
+    }
+
+    /// The weight of a sample `dx` and `dy` pixels from the pixel center
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        self.weight_1d(dx) * self.weight_1d(dy)
+    }
+
+    fn weight_1d(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        if x > self.radius() {
+            return 0.0;
+        }
+
+        match self {
+            Filter::Box => 1.0,
+            Filter::Tent => 1.0 - x,
+            Filter::Gaussian => {
+                // Shifted down so the curve reaches zero at the radius
+                const ALPHA: f64 = 2.0;
+                (-ALPHA * x * x).exp() - (-ALPHA * self.radius() * self.radius()).exp()
+            }
+            Filter::Mitchell => {
+                const B: f64 = 1.0 / 3.0;
+                const C: f64 = 1.0 / 3.0;
+
+                let polynomial = if x < 1.0 {
+                    (12.0 - 9.0 * B - 6.0 * C) * x.powi(3)
+                        + (-18.0 + 12.0 * B + 6.0 * C) * x.powi(2)
+                        + (6.0 - 2.0 * B)
+                } else {
+                    (-B - 6.0 * C) * x.powi(3)
+                        + (6.0 * B + 30.0 * C) * x.powi(2)
+                        + (-12.0 * B - 48.0 * C) * x
+                        + (8.0 * B + 24.0 * C)
+                };
+
+                polynomial / 6.0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::comparison::ApproxEq;
+    use crate::Color;
+    use crate::sampling::{AdaptiveSampling, Filter, SampleStrategy};
+
+    #[test]
+    fn grid_samples_are_evenly_spaced() {
+        let offsets = SampleStrategy::Grid.offsets(4);
+
+        assert_eq!(vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)], offsets);
+        assert_eq!(vec![(0.5, 0.5)], SampleStrategy::Grid.offsets(1));
+    }
+
+    #[test]
+    fn grid_sampling_rounds_down_to_a_square_number_of_samples() {
+        assert_eq!(4, SampleStrategy::Grid.offsets(8).len());
+        assert_eq!(9, SampleStrategy::Jittered.offsets(10).len());
+        assert_eq!(10, SampleStrategy::Random.offsets(10).len());
+        assert_eq!(4, SampleStrategy::Grid.sample_count(8));
+        assert_eq!(9, SampleStrategy::Jittered.sample_count(10));
+        assert_eq!(10, SampleStrategy::Random.sample_count(10));
+    }
+
+    #[test]
+    fn jittered_samples_stay_within_their_cells() {
+        let offsets = SampleStrategy::Jittered.offsets(16);
+
+        for (i, (x, y)) in offsets.into_iter().enumerate() {
+            let (column, row) = ((i % 4) as f64, (i / 4) as f64);
+
+            assert!((column / 4.0..=(column + 1.0) / 4.0).contains(&x));
+            assert!((row / 4.0..=(row + 1.0) / 4.0).contains(&y));
+        }
+    }
+
+    #[test]
+    fn random_samples_are_within_the_unit_square() {
+        for (x, y) in SampleStrategy::Random.offsets(100) {
+            assert!((0.0..1.0).contains(&x));
+            assert!((0.0..1.0).contains(&y));
+        }
+    }
+
+    #[test]
+    fn filters_weigh_samples_by_distance() {
+        let cases = [
+            (Filter::Box, 0.0, 1.0),
+            (Filter::Box, 0.4, 1.0),
+            (Filter::Box, 0.6, 0.0),
+            (Filter::Tent, 0.0, 1.0),
+            (Filter::Tent, 0.5, 0.5),
+            (Filter::Tent, 1.0, 0.0),
+            (Filter::Gaussian, 0.0, 0.98889),
+            (Filter::Gaussian, 1.0, 0.12423),
+            (Filter::Gaussian, 1.5, 0.0),
+            (Filter::Mitchell, 0.0, 0.88889),
+            (Filter::Mitchell, 1.0, 0.05556),
+            (Filter::Mitchell, 1.5, -0.03472),
+            (Filter::Mitchell, 2.0, 0.0),
+        ];
+
+        for (filter, x, expected) in cases {
+            assert!(expected.approx_eq_low_precision(filter.weight_1d(x)), "{:?} at {}", filter, x);
+        }
+    }
+
+    #[test]
+    fn filter_weights_are_separable() {
+        let filter = Filter::Tent;
+
+        assert!(0.25_f64.approx_eq(filter.weight(0.5, 0.5)));
+        assert!(0.0_f64.approx_eq(filter.weight(0.5, 1.0)));
+    }
+
+    #[test]
+    fn adaptive_sampling_compares_each_channel_with_its_threshold() {
+        let adaptive = AdaptiveSampling::new(Color::new(0.1, 0.2, 0.3), 16);
+        let base = Color::new(0.5, 0.5, 0.5);
+
+        assert!(!adaptive.exceeds_threshold(base, Color::new(0.55, 0.65, 0.75)));
+        assert!(adaptive.exceeds_threshold(base, Color::new(0.65, 0.5, 0.5)));
+        assert!(adaptive.exceeds_threshold(base, Color::new(0.5, 0.25, 0.5)));
+        assert!(adaptive.exceeds_threshold(base, Color::new(0.5, 0.5, 0.85)));
+    }
+}
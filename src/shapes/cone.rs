@@ -0,0 +1,277 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::comparison::EPSILON;
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cone {
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Cone {
+    pub fn new() -> Self {
+        Cone {
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn with_minimum(mut self, minimum: f64) -> Self {
+        self.minimum = minimum;
+        self
+    }
+
+    pub fn with_maximum(mut self, maximum: f64) -> Self {
+        self.maximum = maximum;
+        self
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: Ray, xs: &mut Vec<Intersection<'a>>) {
+        // Caps only matter on a closed cone the ray isn't parallel to
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+
+        // The radius of a cone's cap is the distance of the cap from the tip
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y) / ray.direction.y;
+
+            if check_cap(ray, t, y.abs()) {
+                xs.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone::new()
+    }
+}
+
+fn check_cap(ray: Ray, t: f64, radius: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+
+    (x * x + z * z) <= radius * radius
+}
+
+impl RayInteractable for Cone {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        let origin = transformed_ray.origin;
+        let direction = transformed_ray.direction;
+        let mut xs = vec![];
+
+        let a = direction.x * direction.x - direction.y * direction.y + direction.z * direction.z;
+        let b = 2.0 * origin.x * direction.x - 2.0 * origin.y * direction.y + 2.0 * origin.z * direction.z;
+        let c = origin.x * origin.x - origin.y * origin.y + origin.z * origin.z;
+
+        let mut ts = vec![];
+
+        if a.abs() < EPSILON {
+            // The ray is parallel to one of the cone's halves and can hit
+            // the other half at most once
+            if b.abs() >= EPSILON {
+                ts.push(-c / (2.0 * b));
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+
+            if discriminant < 0.0 {
+                return vec![];
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            ts.push(t0.min(t1));
+            ts.push(t0.max(t1));
+        }
+
+        for t in ts {
+            let y = origin.y + t * direction.y;
+
+            if self.minimum < y && y < self.maximum {
+                xs.push(Intersection::new(t, self));
+            }
+        }
+
+        self.intersect_caps(transformed_ray, &mut xs);
+
+        xs
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let object_point = self.world_to_object(point);
+        let distance = object_point.x * object_point.x + object_point.z * object_point.z;
+        let cap_radius = object_point.y * object_point.y;
+
+        let object_normal = if distance < cap_radius && object_point.y >= self.maximum - EPSILON {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else if distance < cap_radius && object_point.y <= self.minimum + EPSILON {
+            Tuple::vector(0.0, -1.0, 0.0)
+        } else {
+            let mut y = distance.sqrt();
+
+            if object_point.y > 0.0 {
+                y = -y;
+            }
+
+            Tuple::vector(object_point.x, y, object_point.z)
+        };
+
+        self.normal_to_world(object_normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+
+        BoundingBox::new(Tuple::point(-limit, self.minimum, -limit), Tuple::point(limit, self.maximum, limit))
+    }
+}
+
+impl Transform for Cone {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Cone {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ray::Ray;
+    use crate::shapes::cone::Cone;
+    use crate::Tuple;
+    use crate::comparison::ApproxEq;
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn intersecting_a_cone_with_a_ray() {
+        let shape = Cone::new();
+        let cases = [
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 5.0, 5.0),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(1.0, 1.0, 1.0), 8.66025, 8.66025),
+            (Tuple::point(1.0, 1.0, -5.0), Tuple::vector(-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let xs = shape.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(2, xs.len());
+            assert!(t0.approx_eq_low_precision(xs[0].t));
+            assert!(t1.approx_eq_low_precision(xs[1].t));
+        }
+    }
+
+    #[test]
+    fn intersecting_a_cone_with_ray_parallel_to_one_of_its_halves() {
+        let shape = Cone::new();
+        let direction = Tuple::vector(0.0, 1.0, 1.0).normalize();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -1.0), direction);
+
+        let xs = shape.intersect(r);
+
+        assert_eq!(1, xs.len());
+        assert!(0.35355.approx_eq_low_precision(xs[0].t));
+    }
+
+    #[test]
+    fn intersecting_the_caps_of_a_closed_cone() {
+        let shape = Cone::new().with_minimum(-0.5).with_maximum(0.5).with_closed(true);
+        let cases = [
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0), 0),
+            (Tuple::point(0.0, 0.0, -0.25), Tuple::vector(0.0, 1.0, 1.0), 2),
+            (Tuple::point(0.0, 0.0, -0.25), Tuple::vector(0.0, 1.0, 0.0), 4),
+        ];
+
+        for (origin, direction, count) in cases {
+            let xs = shape.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(count, xs.len());
+        }
+    }
+
+    #[test]
+    fn computing_the_normal_vector_on_a_cone() {
+        let shape = Cone::new();
+        let sqrt_two = f64::sqrt(2.0);
+        let cases = [
+            (Tuple::point(1.0, 1.0, 1.0), Tuple::vector(1.0, -sqrt_two, 1.0)),
+            (Tuple::point(-1.0, -1.0, 0.0), Tuple::vector(-1.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected.normalize(), shape.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn normal_vector_on_cone_end_caps() {
+        let shape = Cone::new().with_minimum(-1.0).with_maximum(1.0).with_closed(true);
+
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), shape.normal_at(Tuple::point(0.5, 1.0, 0.0)));
+        assert_eq!(Tuple::vector(0.0, -1.0, 0.0), shape.normal_at(Tuple::point(0.0, -1.0, 0.5)));
+    }
+
+    #[test]
+    fn a_bounded_cone_has_a_bounding_box() {
+        let shape = Cone::new().with_minimum(-5.0).with_maximum(3.0);
+
+        let b = shape.bounds();
+
+        assert_eq!(Tuple::point(-5.0, -5.0, -5.0), b.min);
+        assert_eq!(Tuple::point(5.0, 3.0, 5.0), b.max);
+    }
+}
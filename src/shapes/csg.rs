@@ -0,0 +1,305 @@
+use crate::intersection::{Intersection, Intersections};
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::materials::Material;
+use crate::shapes::shape_enum::{RayInteractable, Shape};
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    pub fn intersection_allowed(&self, left_hit: bool, inside_left: bool, inside_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !inside_right) || (!left_hit && !inside_left),
+            CsgOperation::Intersection => (left_hit && inside_right) || (!left_hit && inside_left),
+            CsgOperation::Difference => (left_hit && !inside_right) || (!left_hit && inside_left),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Csg {
+    pub operation: CsgOperation,
+    transform: Matrix4,
+    inverse_transform: Option<Matrix4>,
+    parent_transform: Matrix4,
+    left: Box<Shape>,
+    right: Box<Shape>,
+    material: Material,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Shape, right: Shape) -> Self {
+        let mut csg = Csg {
+            operation,
+            transform: Matrix4::identity_matrix(),
+            inverse_transform: Some(Matrix4::identity_matrix()),
+            parent_transform: Matrix4::identity_matrix(),
+            left: Box::new(left),
+            right: Box::new(right),
+            material: Material::default(),
+        };
+
+        csg.update_children();
+        csg
+    }
+
+    pub fn union(left: Shape, right: Shape) -> Self {
+        Csg::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: Shape, right: Shape) -> Self {
+        Csg::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: Shape, right: Shape) -> Self {
+        Csg::new(CsgOperation::Difference, left, right)
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self.inverse_transform = transform.inverse();
+        self.update_children();
+        self
+    }
+
+    pub fn left(&self) -> &Shape {
+        &self.left
+    }
+
+    pub fn right(&self) -> &Shape {
+        &self.right
+    }
+
+    pub fn divide(&mut self, threshold: usize) {
+        self.left.divide(threshold);
+        self.right.divide(threshold);
+    }
+
+    /// `xs` must be sorted, and every hit must be on one of the children
+    pub fn filter_intersections<'a>(&self, xs: &mut Intersections<'a>) {
+        let mut inside_left = false;
+        let mut inside_right = false;
+
+        xs.retain(|i| {
+            let left_hit = self.left.includes(i.object);
+            let allowed = self.operation.intersection_allowed(left_hit, inside_left, inside_right);
+
+            // Every hit on a child enters or leaves that child
+            if left_hit {
+                inside_left = !inside_left;
+            } else {
+                inside_right = !inside_right;
+            }
+
+            allowed
+        });
+    }
+
+    fn update_children(&mut self) {
+        let world_transform = self.parent_transform * self.transform;
+
+        self.left.set_parent_transform(world_transform);
+        self.right.set_parent_transform(world_transform);
+    }
+}
+
+impl RayInteractable for Csg {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.inverse_transform {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        if !self.bounds().intersects(transformed_ray) {
+            return vec![];
+        }
+
+        let mut xs = self.left.intersect(transformed_ray);
+        xs.extend(self.right.intersect(transformed_ray));
+
+        let mut xs = Intersections::new_from_vec(xs);
+        self.filter_intersections(&mut xs);
+
+        xs.into_vec()
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        // Like groups, intersections refer to the child that was hit
+        panic!("A CSG shape has no surface of its own; ask the child that was hit for its normal")
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+        self.update_children();
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = self.left.parent_space_bounds();
+        bounds.merge(self.right.parent_space_bounds());
+
+        bounds
+    }
+
+    fn includes(&self, object: &dyn RayInteractable) -> bool {
+        self.left.includes(object) || self.right.includes(object)
+    }
+}
+
+impl Transform for Csg {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        self.with_transform(new_transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intersection::{Intersection, Intersections};
+    use crate::ray::Ray;
+    use crate::shapes::csg::{Csg, CsgOperation};
+    use crate::shapes::{Cube, Group, Shape, Sphere};
+    use crate::{scaling, translation, Tuple};
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn csg_is_created_with_an_operation_and_two_shapes() {
+        let c = Csg::union(Shape::Sphere(Sphere::new()), Shape::Cube(Cube::new()))
+            .with_transform(translation(1.0, 0.0, 0.0));
+
+        assert_eq!(CsgOperation::Union, c.operation);
+        assert!(matches!(c.left(), Shape::Sphere(_)));
+        assert!(matches!(c.right(), Shape::Cube(_)));
+        assert_eq!(translation(1.0, 0.0, 0.0), c.left().parent_transform());
+        assert_eq!(translation(1.0, 0.0, 0.0), c.right().parent_transform());
+    }
+
+    #[test]
+    fn evaluating_the_rule_for_a_csg_operation() {
+        use CsgOperation::*;
+
+        let cases = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+
+        for (operation, left_hit, inside_left, inside_right, expected) in cases {
+            assert_eq!(expected, operation.intersection_allowed(left_hit, inside_left, inside_right));
+        }
+    }
+
+    #[test]
+    fn filtering_a_list_of_intersections() {
+        let cases = [
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+
+        for (operation, x0, x1) in cases {
+            let c = Csg::new(operation, Shape::Sphere(Sphere::new()), Shape::Cube(Cube::new()));
+            let (s1, s2) = (c.left(), c.right());
+            let mut xs = Intersections::new_from_vec(vec![
+                Intersection::new(1.0, s1),
+                Intersection::new(2.0, s2),
+                Intersection::new(3.0, s1),
+                Intersection::new(4.0, s2),
+            ]);
+
+            c.filter_intersections(&mut xs);
+
+            assert_eq!(2, xs.len());
+            assert_eq!(x0 as f64 + 1.0, xs[0].t);
+            assert_eq!(x1 as f64 + 1.0, xs[1].t);
+        }
+    }
+
+    #[test]
+    fn a_ray_misses_a_csg_object() {
+        let c = Csg::union(Shape::Sphere(Sphere::new()), Shape::Cube(Cube::new()));
+        let r = Ray::new(Tuple::point(0.0, 2.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(c.intersect(r).is_empty());
+    }
+
+    #[test]
+    fn a_ray_hits_a_csg_object() {
+        let s2 = Sphere::new().with_transform(translation(0.0, 0.0, 0.5));
+        let c = Csg::union(Shape::Sphere(Sphere::new()), Shape::Sphere(s2));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = c.intersect(r);
+
+        assert_eq!(2, xs.len());
+        assert_eq!(4.0, xs[0].t);
+        assert!(c.left().includes(xs[0].object));
+        assert_eq!(6.5, xs[1].t);
+        assert!(c.right().includes(xs[1].object));
+    }
+
+    #[test]
+    fn a_difference_carves_a_hole() {
+        let hole = Sphere::new().with_transform(scaling(0.5, 0.5, 2.0));
+        let c = Csg::difference(Shape::Cube(Cube::new()), Shape::Sphere(hole));
+        let through_hole = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let beside_hole = Ray::new(Tuple::point(0.75, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(c.intersect(through_hole).is_empty());
+        assert_eq!(2, c.intersect(beside_hole).len());
+    }
+
+    #[test]
+    fn csg_and_groups_include_nested_children() {
+        let g = Group::new().with_children(vec![Shape::Sphere(Sphere::new())]);
+        let c = Csg::intersection(Shape::Group(g), Shape::Cube(Cube::new()));
+        let other = Sphere::new();
+
+        let Shape::Group(g) = c.left() else { panic!("expected a group") };
+        let Shape::Sphere(nested) = &g.children()[0] else { panic!("expected a sphere") };
+
+        assert!(c.includes(nested));
+        assert!(c.left().includes(nested));
+        assert!(!c.right().includes(nested));
+        assert!(!c.includes(&other));
+    }
+}
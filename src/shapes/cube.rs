@@ -0,0 +1,214 @@
+use crate::intersection::Intersection;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::bounds::{BoundingBox, check_axis};
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cube {
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+}
+
+impl Cube {
+    pub fn new() -> Self {
+        Cube {
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new()
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Cube::new()
+    }
+}
+
+impl RayInteractable for Cube {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        let (xtmin, xtmax) = check_axis(transformed_ray.origin.x, transformed_ray.direction.x, -1.0, 1.0);
+        let (ytmin, ytmax) = check_axis(transformed_ray.origin.y, transformed_ray.direction.y, -1.0, 1.0);
+        let (ztmin, ztmax) = check_axis(transformed_ray.origin.z, transformed_ray.direction.z, -1.0, 1.0);
+
+        // The ray is inside the cube between the last slab it enters and
+        // the first slab it leaves
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        if tmin > tmax {
+            return vec![];
+        }
+
+        vec![
+            Intersection::new(tmin, self),
+            Intersection::new(tmax, self),
+        ]
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let object_point = self.world_to_object(point);
+
+        // The face that was hit is the one on the axis with the largest component
+        let x = object_point.x.abs();
+        let y = object_point.y.abs();
+        let z = object_point.z.abs();
+
+        let object_normal = if x >= y && x >= z {
+            Tuple::vector(object_point.x, 0.0, 0.0)
+        } else if y >= z {
+            Tuple::vector(0.0, object_point.y, 0.0)
+        } else {
+            Tuple::vector(0.0, 0.0, object_point.z)
+        };
+
+        self.normal_to_world(object_normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+    }
+}
+
+impl Transform for Cube {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Cube {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ray::Ray;
+    use crate::shapes::cube::Cube;
+    use crate::{Tuple, Transform};
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn ray_intersects_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (Tuple::point(5.0, 0.5, 0.0), Tuple::vector(-1.0, 0.0, 0.0), 4.0, 6.0),
+            (Tuple::point(-5.0, 0.5, 0.0), Tuple::vector(1.0, 0.0, 0.0), 4.0, 6.0),
+            (Tuple::point(0.5, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0), 4.0, 6.0),
+            (Tuple::point(0.5, -5.0, 0.0), Tuple::vector(0.0, 1.0, 0.0), 4.0, 6.0),
+            (Tuple::point(0.5, 0.0, 5.0), Tuple::vector(0.0, 0.0, -1.0), 4.0, 6.0),
+            (Tuple::point(0.5, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 4.0, 6.0),
+            (Tuple::point(0.0, 0.5, 0.0), Tuple::vector(0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+
+        for (origin, direction, t1, t2) in cases {
+            let xs = c.intersect(Ray::new(origin, direction));
+
+            assert_eq!(2, xs.len());
+            assert_eq!(t1, xs[0].t);
+            assert_eq!(t2, xs[1].t);
+        }
+    }
+
+    #[test]
+    fn ray_misses_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (Tuple::point(-2.0, 0.0, 0.0), Tuple::vector(0.2673, 0.5345, 0.8018)),
+            (Tuple::point(0.0, -2.0, 0.0), Tuple::vector(0.8018, 0.2673, 0.5345)),
+            (Tuple::point(0.0, 0.0, -2.0), Tuple::vector(0.5345, 0.8018, 0.2673)),
+            (Tuple::point(2.0, 0.0, 2.0), Tuple::vector(0.0, 0.0, -1.0)),
+            (Tuple::point(0.0, 2.0, 2.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(2.0, 2.0, 0.0), Tuple::vector(-1.0, 0.0, 0.0)),
+        ];
+
+        for (origin, direction) in cases {
+            let xs = c.intersect(Ray::new(origin, direction));
+
+            assert_eq!(0, xs.len());
+        }
+    }
+
+    #[test]
+    fn normal_on_the_surface_of_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (Tuple::point(1.0, 0.5, -0.8), Tuple::vector(1.0, 0.0, 0.0)),
+            (Tuple::point(-1.0, -0.2, 0.9), Tuple::vector(-1.0, 0.0, 0.0)),
+            (Tuple::point(-0.4, 1.0, -0.1), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.3, -1.0, -0.7), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(-0.6, 0.3, 1.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.4, 0.4, -1.0), Tuple::vector(0.0, 0.0, -1.0)),
+            (Tuple::point(1.0, 1.0, 1.0), Tuple::vector(1.0, 0.0, 0.0)),
+            (Tuple::point(-1.0, -1.0, -1.0), Tuple::vector(-1.0, 0.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, c.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn ray_intersects_a_transformed_cube() {
+        let c = Cube::new().scale(2.0, 1.0, 1.0).translate(0.0, 0.0, 5.0).transform();
+        let r = Ray::new(Tuple::point(-10.0, 0.0, 5.0), Tuple::vector(1.0, 0.0, 0.0));
+
+        let xs = c.intersect(r);
+
+        assert_eq!(2, xs.len());
+        assert_eq!(8.0, xs[0].t);
+        assert_eq!(12.0, xs[1].t);
+    }
+
+    #[test]
+    fn normal_on_a_transformed_cube() {
+        let c = Cube::new().rotate_y(std::f64::consts::PI / 4.0).transform();
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+
+        let n = c.normal_at(Tuple::point(sqrt_two, 0.0, sqrt_two));
+
+        assert_eq!(Tuple::vector(sqrt_two, 0.0, sqrt_two), n);
+    }
+
+    #[test]
+    fn a_cube_has_a_bounding_box() {
+        let b = Cube::new().bounds();
+
+        assert_eq!(Tuple::point(-1.0, -1.0, -1.0), b.min);
+        assert_eq!(Tuple::point(1.0, 1.0, 1.0), b.max);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::comparison::EPSILON;
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cylinder {
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Cylinder {
+    pub fn new() -> Self {
+        Cylinder {
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn with_minimum(mut self, minimum: f64) -> Self {
+        self.minimum = minimum;
+        self
+    }
+
+    pub fn with_maximum(mut self, maximum: f64) -> Self {
+        self.maximum = maximum;
+        self
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: Ray, xs: &mut Vec<Intersection<'a>>) {
+        // Caps only matter on a closed cylinder the ray isn't parallel to
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y) / ray.direction.y;
+
+            if check_cap(ray, t) {
+                xs.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder::new()
+    }
+}
+
+fn check_cap(ray: Ray, t: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+
+    (x * x + z * z) <= 1.0
+}
+
+impl RayInteractable for Cylinder {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        let origin = transformed_ray.origin;
+        let direction = transformed_ray.direction;
+        let mut xs = vec![];
+
+        let a = direction.x * direction.x + direction.z * direction.z;
+
+        // A ray parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * origin.x * direction.x + 2.0 * origin.z * direction.z;
+            let c = origin.x * origin.x + origin.z * origin.z - 1.0;
+
+            let discriminant = b * b - 4.0 * a * c;
+
+            if discriminant < 0.0 {
+                return vec![];
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            for t in [t0.min(t1), t0.max(t1)] {
+                let y = origin.y + t * direction.y;
+
+                if self.minimum < y && y < self.maximum {
+                    xs.push(Intersection::new(t, self));
+                }
+            }
+        }
+
+        self.intersect_caps(transformed_ray, &mut xs);
+
+        xs
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let object_point = self.world_to_object(point);
+        let distance = object_point.x * object_point.x + object_point.z * object_point.z;
+
+        let object_normal = if distance < 1.0 && object_point.y >= self.maximum - EPSILON {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else if distance < 1.0 && object_point.y <= self.minimum + EPSILON {
+            Tuple::vector(0.0, -1.0, 0.0)
+        } else {
+            Tuple::vector(object_point.x, 0.0, object_point.z)
+        };
+
+        self.normal_to_world(object_normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Tuple::point(-1.0, self.minimum, -1.0), Tuple::point(1.0, self.maximum, 1.0))
+    }
+}
+
+impl Transform for Cylinder {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Cylinder {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ray::Ray;
+    use crate::shapes::cylinder::Cylinder;
+    use crate::Tuple;
+    use crate::comparison::ApproxEq;
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn ray_misses_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (Tuple::point(1.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(1.0, 1.0, 1.0)),
+        ];
+
+        for (origin, direction) in cases {
+            let xs = cyl.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(0, xs.len());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (Tuple::point(1.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 5.0, 5.0),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 4.0, 6.0),
+            (Tuple::point(0.5, 0.0, -5.0), Tuple::vector(0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let xs = cyl.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(2, xs.len());
+            assert!(t0.approx_eq_low_precision(xs[0].t));
+            assert!(t1.approx_eq_low_precision(xs[1].t));
+        }
+    }
+
+    #[test]
+    fn normal_vector_on_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (Tuple::point(1.0, 0.0, 0.0), Tuple::vector(1.0, 0.0, 0.0)),
+            (Tuple::point(0.0, 5.0, -1.0), Tuple::vector(0.0, 0.0, -1.0)),
+            (Tuple::point(0.0, -2.0, 1.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(-1.0, 1.0, 0.0), Tuple::vector(-1.0, 0.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, cyl.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn default_cylinder_is_infinite_and_open() {
+        let cyl = Cylinder::new();
+
+        assert_eq!(f64::NEG_INFINITY, cyl.minimum);
+        assert_eq!(f64::INFINITY, cyl.maximum);
+        assert!(!cyl.closed);
+    }
+
+    #[test]
+    fn intersecting_a_constrained_cylinder() {
+        let cyl = Cylinder::new().with_minimum(1.0).with_maximum(2.0);
+        let cases = [
+            (Tuple::point(0.0, 1.5, 0.0), Tuple::vector(0.1, 1.0, 0.0), 0),
+            (Tuple::point(0.0, 3.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 0),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 0),
+            (Tuple::point(0.0, 2.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 0),
+            (Tuple::point(0.0, 1.0, -5.0), Tuple::vector(0.0, 0.0, 1.0), 0),
+            (Tuple::point(0.0, 1.5, -2.0), Tuple::vector(0.0, 0.0, 1.0), 2),
+        ];
+
+        for (origin, direction, count) in cases {
+            let xs = cyl.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(count, xs.len());
+        }
+    }
+
+    #[test]
+    fn intersecting_the_caps_of_a_closed_cylinder() {
+        let cyl = Cylinder::new().with_minimum(1.0).with_maximum(2.0).with_closed(true);
+        let cases = [
+            (Tuple::point(0.0, 3.0, 0.0), Tuple::vector(0.0, -1.0, 0.0), 2),
+            (Tuple::point(0.0, 3.0, -2.0), Tuple::vector(0.0, -1.0, 2.0), 2),
+            (Tuple::point(0.0, 4.0, -2.0), Tuple::vector(0.0, -1.0, 1.0), 2),
+            (Tuple::point(0.0, 0.0, -2.0), Tuple::vector(0.0, 1.0, 2.0), 2),
+            (Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 1.0), 2),
+        ];
+
+        for (origin, direction, count) in cases {
+            let xs = cyl.intersect(Ray::new(origin, direction.normalize()));
+
+            assert_eq!(count, xs.len());
+        }
+    }
+
+    #[test]
+    fn normal_vector_on_cylinder_end_caps() {
+        let cyl = Cylinder::new().with_minimum(1.0).with_maximum(2.0).with_closed(true);
+        let cases = [
+            (Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.5, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 1.0, 0.5), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 2.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.5, 2.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 2.0, 0.5), Tuple::vector(0.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, cyl.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn a_bounded_cylinder_has_a_bounding_box() {
+        let cyl = Cylinder::new().with_minimum(-5.0).with_maximum(3.0);
+
+        let b = cyl.bounds();
+
+        assert_eq!(Tuple::point(-1.0, -5.0, -1.0), b.min);
+        assert_eq!(Tuple::point(1.0, 3.0, 1.0), b.max);
+    }
+}
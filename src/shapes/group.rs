@@ -0,0 +1,546 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::materials::Material;
+use crate::shapes::shape_enum::{RayInteractable, Shape};
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    transform: Matrix4,
+    inverse_transform: Option<Matrix4>,
+    parent_transform: Matrix4,
+    children: Vec<Shape>,
+    bounds: BoundingBox,
+    material: Material,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Group {
+            transform: Matrix4::identity_matrix(),
+            inverse_transform: Some(Matrix4::identity_matrix()),
+            parent_transform: Matrix4::identity_matrix(),
+            children: vec![],
+            bounds: BoundingBox::empty(),
+            material: Material::default(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self.inverse_transform = transform.inverse();
+        self.update_children();
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<Shape>) -> Self {
+        for child in children {
+            self.add_child(child);
+        }
+
+        self
+    }
+
+    pub fn add_child(&mut self, mut child: Shape) {
+        child.set_parent_transform(self.parent_transform * self.transform);
+        self.bounds.merge(child.parent_space_bounds());
+        self.children.push(child);
+    }
+
+    pub fn children(&self) -> &[Shape] {
+        &self.children
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Splits groups with at least `threshold` children into a bounding
+    /// volume hierarchy
+    pub fn divide(&mut self, threshold: usize) {
+        if threshold <= self.children.len() {
+            let (left, right) = self.partition_children();
+
+            if !left.is_empty() {
+                self.add_child(Shape::Group(Group::new().with_children(left)));
+            }
+
+            if !right.is_empty() {
+                self.add_child(Shape::Group(Group::new().with_children(right)));
+            }
+        }
+
+        for child in self.children.iter_mut() {
+            child.divide(threshold);
+        }
+    }
+
+    /// Children straddling the split, and infinite ones like planes, stay in
+    /// the group
+    fn partition_children(&mut self) -> (Vec<Shape>, Vec<Shape>) {
+        let mut finite_bounds = BoundingBox::empty();
+        for child in self.children.iter() {
+            let bounds = child.parent_space_bounds();
+            if bounds.is_finite() {
+                finite_bounds.merge(bounds);
+            }
+        }
+
+        if finite_bounds.is_empty() {
+            return (vec![], vec![]);
+        }
+
+        let (left_bounds, right_bounds) = finite_bounds.split();
+
+        let sides: Vec<(bool, bool)> = self.children
+            .iter()
+            .map(|child| {
+                let bounds = child.parent_space_bounds();
+                let left = left_bounds.contains_box(bounds);
+                (left, !left && right_bounds.contains_box(bounds))
+            })
+            .collect();
+
+        // Moving every child to the same side would only nest the group
+        // again, which happens when the children can't be told apart
+        let left_count = sides.iter().filter(|(left, _)| *left).count();
+        let right_count = sides.iter().filter(|(_, right)| *right).count();
+
+        if left_count == self.children.len() || right_count == self.children.len() {
+            return (vec![], vec![]);
+        }
+
+        let (mut left, mut right) = (vec![], vec![]);
+
+        for (child, (in_left, in_right)) in std::mem::take(&mut self.children).into_iter().zip(sides) {
+            if in_left {
+                left.push(child);
+            } else if in_right {
+                right.push(child);
+            } else {
+                self.children.push(child);
+            }
+        }
+
+        (left, right)
+    }
+
+    fn update_children(&mut self) {
+        let world_transform = self.parent_transform * self.transform;
+
+        for child in self.children.iter_mut() {
+            child.set_parent_transform(world_transform);
+        }
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Group::new()
+    }
+}
+
+impl RayInteractable for Group {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.inverse_transform {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        if !self.bounds.intersects(transformed_ray) {
+            return vec![];
+        }
+
+        let mut xs: Vec<Intersection> = self.children
+            .iter()
+            .flat_map(|child| child.intersect(transformed_ray))
+            .collect();
+
+        xs.sort_unstable_by(|a, b| a.t.partial_cmp(&b.t).unwrap());
+
+        xs
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        // Intersections always refer to the child that was hit, so a group
+        // is never asked for a normal while rendering
+        panic!("A group has no surface of its own; ask the child that was hit for its normal")
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+        self.update_children();
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn includes(&self, object: &dyn RayInteractable) -> bool {
+        self.children.iter().any(|child| child.includes(object))
+    }
+}
+
+impl Transform for Group {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        self.with_transform(new_transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use std::time::Instant;
+    use crate::ray::Ray;
+    use crate::shapes::group::Group;
+    use crate::shapes::{Cylinder, Plane, Shape, Sphere, Triangle};
+    use crate::{Matrix4, rotation_y, scaling, translation, Tuple};
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn creating_a_new_group() {
+        let g = Group::new();
+
+        assert_eq!(Matrix4::identity_matrix(), g.local_transform());
+        assert!(g.is_empty());
+    }
+
+    #[test]
+    fn adding_a_child_to_a_group() {
+        let g = Group::new().with_transform(translation(1.0, 2.0, 3.0));
+        let s = Sphere::new();
+
+        let g = g.with_children(vec![Shape::Sphere(s)]);
+
+        assert_eq!(1, g.children().len());
+        assert_eq!(translation(1.0, 2.0, 3.0), g.children()[0].parent_transform());
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_an_empty_group() {
+        let g = Group::new();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = g.intersect(r);
+
+        assert!(xs.is_empty());
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_a_nonempty_group() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new().with_transform(translation(0.0, 0.0, -3.0));
+        let s3 = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let g = Group::new().with_children(vec![
+            Shape::Sphere(s1),
+            Shape::Sphere(s2),
+            Shape::Sphere(s3),
+        ]);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = g.intersect(r);
+
+        let (Shape::Sphere(s1), Shape::Sphere(s2)) = (&g.children()[0], &g.children()[1]) else {
+            panic!("expected spheres")
+        };
+
+        assert_eq!(4, xs.len());
+        assert!(std::ptr::addr_eq(xs[0].object, s2));
+        assert!(std::ptr::addr_eq(xs[1].object, s2));
+        assert!(std::ptr::addr_eq(xs[2].object, s1));
+        assert!(std::ptr::addr_eq(xs[3].object, s1));
+    }
+
+    #[test]
+    fn intersecting_a_transformed_group() {
+        let s = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let g = Group::new()
+            .with_transform(scaling(2.0, 2.0, 2.0))
+            .with_children(vec![Shape::Sphere(s)]);
+        let r = Ray::new(Tuple::point(10.0, 0.0, -10.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = g.intersect(r);
+
+        assert_eq!(2, xs.len());
+    }
+
+    #[test]
+    fn converting_a_point_from_world_to_object_space() {
+        let s = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let g2 = Group::new()
+            .with_transform(scaling(2.0, 2.0, 2.0))
+            .with_children(vec![Shape::Sphere(s)]);
+        let g1 = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_children(vec![Shape::Group(g2)]);
+
+        let Shape::Group(g2) = &g1.children()[0] else { panic!("expected a group") };
+        let s = &g2.children()[0];
+
+        let p = s.world_to_object(Tuple::point(-2.0, 0.0, -10.0));
+
+        assert_eq!(Tuple::point(0.0, 0.0, -1.0), p);
+    }
+
+    #[test]
+    fn converting_a_normal_from_object_to_world_space() {
+        let s = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let g2 = Group::new()
+            .with_transform(scaling(1.0, 2.0, 3.0))
+            .with_children(vec![Shape::Sphere(s)]);
+        let g1 = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_children(vec![Shape::Group(g2)]);
+
+        let Shape::Group(g2) = &g1.children()[0] else { panic!("expected a group") };
+        let s = &g2.children()[0];
+        let sqrt_three = f64::sqrt(3.0) / 3.0;
+
+        let n = s.normal_to_world(Tuple::vector(sqrt_three, sqrt_three, sqrt_three));
+
+        assert_eq!(Tuple::vector(0.2857, 0.4286, -0.8571), n);
+    }
+
+    #[test]
+    fn finding_the_normal_on_a_child_object() {
+        let s = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let g2 = Group::new()
+            .with_transform(scaling(1.0, 2.0, 3.0))
+            .with_children(vec![Shape::Sphere(s)]);
+        let g1 = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_children(vec![Shape::Group(g2)]);
+
+        let Shape::Group(g2) = &g1.children()[0] else { panic!("expected a group") };
+        let s = &g2.children()[0];
+
+        let n = s.normal_at(Tuple::point(1.7321, 1.1547, -5.5774));
+
+        assert_eq!(Tuple::vector(0.2857, 0.4286, -0.8571), n);
+    }
+
+    #[test]
+    fn transforming_a_group_updates_its_children() {
+        let g = Group::new()
+            .with_children(vec![Shape::Sphere(Sphere::new())])
+            .with_transform(translation(0.0, 1.0, 0.0));
+
+        let child = &g.children()[0];
+
+        assert_eq!(translation(0.0, 1.0, 0.0), child.parent_transform());
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), child.normal_at(Tuple::point(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn a_group_has_a_bounding_box_that_contains_its_children() {
+        let s = Sphere::new().with_transform(translation(2.0, 5.0, -3.0) * scaling(2.0, 2.0, 2.0));
+        let c = Cylinder::new()
+            .with_minimum(-2.0)
+            .with_maximum(2.0)
+            .with_transform(translation(-4.0, -1.0, 4.0) * scaling(0.5, 1.0, 0.5));
+        let g = Group::new().with_children(vec![Shape::Sphere(s), Shape::Cylinder(c)]);
+
+        let b = g.bounds();
+
+        assert_eq!(Tuple::point(-4.5, -3.0, -5.0), b.min);
+        assert_eq!(Tuple::point(4.0, 7.0, 4.5), b.max);
+    }
+
+    #[test]
+    fn intersecting_ray_and_group_does_not_test_children_if_box_is_missed() {
+        let g = Group::new().with_children(vec![Shape::Sphere(Sphere::new())]);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert!(g.intersect(r).is_empty());
+    }
+
+    #[test]
+    fn partitioning_a_groups_children() {
+        let s1 = Sphere::new().with_transform(translation(-2.0, 0.0, 0.0));
+        let s2 = Sphere::new().with_transform(translation(2.0, 0.0, 0.0));
+        let s3 = Sphere::new();
+        let mut g = Group::new().with_children(vec![
+            Shape::Sphere(s1.clone()),
+            Shape::Sphere(s2.clone()),
+            Shape::Sphere(s3.clone()),
+        ]);
+
+        let (left, right) = g.partition_children();
+
+        assert_eq!(1, g.children().len());
+        assert_eq!(s3.transform, g.children()[0].local_transform());
+        assert_eq!(1, left.len());
+        assert_eq!(s1.transform, left[0].local_transform());
+        assert_eq!(1, right.len());
+        assert_eq!(s2.transform, right[0].local_transform());
+    }
+
+    #[test]
+    fn subdividing_a_group_partitions_its_children() {
+        let s1 = Sphere::new().with_transform(translation(-2.0, -2.0, 0.0));
+        let s2 = Sphere::new().with_transform(translation(-2.0, 2.0, 0.0));
+        let s3 = Sphere::new().with_transform(scaling(4.0, 4.0, 4.0));
+        let mut g = Group::new().with_children(vec![
+            Shape::Sphere(s1.clone()),
+            Shape::Sphere(s2.clone()),
+            Shape::Sphere(s3.clone()),
+        ]);
+
+        g.divide(1);
+
+        assert_eq!(2, g.children().len());
+        assert_eq!(s3.transform, g.children()[0].local_transform());
+
+        let Shape::Group(subgroup) = &g.children()[1] else { panic!("expected a subgroup") };
+
+        assert_eq!(2, subgroup.children().len());
+        assert!(matches!(subgroup.children()[0], Shape::Group(_)));
+        assert!(matches!(subgroup.children()[1], Shape::Group(_)));
+    }
+
+    #[test]
+    fn subdividing_a_group_with_too_few_children() {
+        let s1 = Sphere::new().with_transform(translation(-2.0, 0.0, 0.0));
+        let s2 = Sphere::new().with_transform(translation(2.0, 1.0, 0.0));
+        let s3 = Sphere::new().with_transform(translation(2.0, -1.0, 0.0));
+        let s4 = Sphere::new();
+        let subgroup = Group::new().with_children(vec![
+            Shape::Sphere(s1.clone()),
+            Shape::Sphere(s2.clone()),
+            Shape::Sphere(s3.clone()),
+        ]);
+        let mut g = Group::new().with_children(vec![Shape::Group(subgroup), Shape::Sphere(s4.clone())]);
+
+        g.divide(3);
+
+        let Shape::Group(subgroup) = &g.children()[0] else { panic!("expected a subgroup") };
+
+        assert_eq!(2, g.children().len());
+        assert_eq!(s4.transform, g.children()[1].local_transform());
+
+        let (Shape::Group(left), Shape::Group(right)) = (&subgroup.children()[0], &subgroup.children()[1]) else {
+            panic!("expected two subgroups")
+        };
+
+        assert_eq!(2, subgroup.children().len());
+        assert_eq!(1, left.children().len());
+        assert_eq!(2, right.children().len());
+    }
+
+    #[test]
+    fn subdividing_a_group_containing_a_plane() {
+        let mut g = Group::new().with_children(vec![
+            Shape::Plane(Plane::new()),
+            Shape::Sphere(Sphere::new().with_transform(translation(-4.0, 0.0, 0.0))),
+            Shape::Sphere(Sphere::new().with_transform(translation(-2.0, 0.0, 0.0))),
+            Shape::Sphere(Sphere::new().with_transform(translation(2.0, 0.0, 0.0))),
+            Shape::Sphere(Sphere::new().with_transform(translation(4.0, 0.0, 0.0))),
+        ]);
+
+        g.divide(4);
+
+        let (Shape::Group(left), Shape::Group(right)) = (&g.children()[1], &g.children()[2]) else {
+            panic!("expected two subgroups")
+        };
+
+        assert_eq!(3, g.children().len());
+        assert!(matches!(g.children()[0], Shape::Plane(_)));
+        assert_eq!(2, left.children().len());
+        assert_eq!(2, right.children().len());
+    }
+
+    #[test]
+    fn dividing_identical_children_leaves_the_group_alone() {
+        let mut g = Group::new().with_children(vec![
+            Shape::Sphere(Sphere::new()),
+            Shape::Sphere(Sphere::new()),
+        ]);
+
+        g.divide(1);
+
+        assert_eq!(2, g.children().len());
+        assert!(g.children().iter().all(|child| matches!(child, Shape::Sphere(_))));
+    }
+
+    /// A flat group of `size` by `size` squares of two triangles each,
+    /// spanning x and y from 0 to `size`
+    fn triangle_grid(size: usize) -> Group {
+        let mut group = Group::new();
+
+        for row in 0..size {
+            for col in 0..size {
+                let (x, y) = (col as f64, row as f64);
+                let p1 = Tuple::point(x, y, 0.0);
+                let p2 = Tuple::point(x + 1.0, y, 0.0);
+                let p3 = Tuple::point(x + 1.0, y + 1.0, 0.0);
+                let p4 = Tuple::point(x, y + 1.0, 0.0);
+
+                group.add_child(Shape::Triangle(Triangle::new(p1, p2, p3)));
+                group.add_child(Shape::Triangle(Triangle::new(p1, p3, p4)));
+            }
+        }
+
+        group
+    }
+
+    #[test]
+    fn a_divided_group_is_hit_the_same_as_a_flat_one() {
+        let flat = triangle_grid(8).with_transform(translation(-4.0, -4.0, 0.0));
+        let mut divided = flat.clone();
+        divided.divide(4);
+
+        for i in 0..100 {
+            let (x, y) = ((i % 10) as f64 - 4.9, (i / 10) as f64 - 4.8);
+            let r = Ray::new(Tuple::point(x, y, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+            let expected: Vec<f64> = flat.intersect(r).iter().map(|i| i.t).collect();
+            let actual: Vec<f64> = divided.intersect(r).iter().map(|i| i.t).collect();
+
+            assert_eq!(expected, actual);
+        }
+    }
+
+    /// Times intersecting a 20,000 triangle mesh with and without a bounding
+    /// volume hierarchy. Run with `cargo test --release -- --ignored bvh`.
+    #[test]
+    #[ignore]
+    fn bvh_speeds_up_intersecting_a_large_mesh() {
+        let flat = triangle_grid(100);
+        let mut divided = flat.clone();
+        divided.divide(4);
+
+        let rays: Vec<Ray> = (0..1000)
+            .map(|i| {
+                let (x, y) = ((i % 40) as f64 * 2.5, (i / 40) as f64 * 4.0);
+                Ray::new(Tuple::point(x + 0.1, y + 0.2, -5.0), Tuple::vector(0.0, 0.0, 1.0))
+            })
+            .collect();
+
+        let time = |group: &Group| {
+            let start = Instant::now();
+            let hits: usize = rays.iter().map(|r| group.intersect(*r).len()).sum();
+            (start.elapsed(), hits)
+        };
+
+        let (flat_time, flat_hits) = time(&flat);
+        let (divided_time, divided_hits) = time(&divided);
+
+        assert_eq!(flat_hits, divided_hits);
+        assert!(divided_time < flat_time);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::comparison::EPSILON;
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plane {
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+}
+
+impl Plane {
+    pub fn new() -> Self {
+        Plane {
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new()
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane::new()
+    }
+}
+
+impl RayInteractable for Plane {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        // A ray parallel to the plane (or coplanar with it) never hits it
+        if transformed_ray.direction.y.abs() < EPSILON {
+            return vec![];
+        }
+
+        let t = -transformed_ray.origin.y / transformed_ray.direction.y;
+
+        vec![Intersection::new(t, self)]
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        let object_normal = Tuple::vector(0.0, 1.0, 0.0);
+        self.normal_to_world(object_normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY))
+    }
+}
+
+impl Transform for Plane {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Plane {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ray::Ray;
+    use crate::shapes::plane::Plane;
+    use crate::{Matrix4, Tuple, Transform};
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn normal_of_plane_is_constant_everywhere() {
+        let p = Plane::new();
+
+        let n1 = p.normal_at(Tuple::point(0.0, 0.0, 0.0));
+        let n2 = p.normal_at(Tuple::point(10.0, 0.0, -10.0));
+        let n3 = p.normal_at(Tuple::point(-5.0, 0.0, 150.0));
+
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), n1);
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), n2);
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), n3);
+    }
+
+    #[test]
+    fn normal_of_rotated_plane() {
+        let p = Plane::new().rotate_z(std::f64::consts::PI / 2.0).transform();
+
+        let n = p.normal_at(Tuple::point(0.0, 0.0, 0.0));
+
+        assert_eq!(Tuple::vector(-1.0, 0.0, 0.0), n);
+    }
+
+    #[test]
+    fn intersect_with_ray_parallel_to_plane() {
+        let p = Plane::new();
+        let r = Ray::new(Tuple::point(0.0, 10.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = p.intersect(r);
+
+        assert_eq!(0, xs.len());
+    }
+
+    #[test]
+    fn intersect_with_coplanar_ray() {
+        let p = Plane::new();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = p.intersect(r);
+
+        assert_eq!(0, xs.len());
+    }
+
+    #[test]
+    fn ray_intersecting_plane_from_above() {
+        let p = Plane::new();
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        let xs = p.intersect(r);
+
+        assert_eq!(1, xs.len());
+        assert_eq!(1.0, xs[0].t);
+    }
+
+    #[test]
+    fn ray_intersecting_plane_from_below() {
+        let p = Plane::new();
+        let r = Ray::new(Tuple::point(0.0, -1.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        let xs = p.intersect(r);
+
+        assert_eq!(1, xs.len());
+        assert_eq!(1.0, xs[0].t);
+    }
+
+    #[test]
+    fn plane_has_default_transformation() {
+        let p = Plane::new();
+
+        assert_eq!(Matrix4::identity_matrix(), p.transform);
+    }
+
+    #[test]
+    fn ray_intersecting_translated_plane() {
+        let p = Plane::new().translate(0.0, -2.0, 0.0).transform();
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        let xs = p.intersect(r);
+
+        assert_eq!(1, xs.len());
+        assert_eq!(3.0, xs[0].t);
+    }
+
+    #[test]
+    fn normal_of_plane_ignores_translation() {
+        let p = Plane::new().translate(0.0, 5.0, 0.0).transform();
+
+        let n = p.normal_at(Tuple::point(3.0, 5.0, 1.0));
+
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), n);
+    }
+
+    #[test]
+    fn a_plane_has_a_bounding_box() {
+        let b = Plane::new().bounds();
+
+        assert_eq!(Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY), b.min);
+        assert_eq!(Tuple::point(f64::INFINITY, 0.0, f64::INFINITY), b.max);
+    }
+}
@@ -1,38 +1,166 @@
+use std::fmt::Debug;
 use crate::intersection::Intersection;
-use crate::{Material, Ray, Tuple};
+use crate::bounds::BoundingBox;
+use crate::{Material, Matrix4, Ray, Tuple};
+use crate::shapes::cone::Cone;
+use crate::shapes::cube::Cube;
+use crate::shapes::csg::Csg;
+use crate::shapes::cylinder::Cylinder;
+use crate::shapes::group::Group;
+use crate::shapes::plane::Plane;
+use crate::shapes::smooth_triangle::SmoothTriangle;
 use crate::shapes::sphere::Sphere;
+use crate::shapes::triangle::Triangle;
 
 
-pub trait RayInteractable {
-    fn intersect(&self, ray: Ray) -> Vec<Intersection>;
+pub trait RayInteractable: Debug {
+    /// Takes a ray in the space of the shape's parent
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>>;
+
     fn normal_at(&self, point: Tuple) -> Tuple;
-    fn material(&self) -> Material;
+    fn material(&self) -> &Material;
+
+    fn local_transform(&self) -> Matrix4;
+
+    fn parent_transform(&self) -> Matrix4;
+    fn set_parent_transform(&mut self, transform: Matrix4);
+
+    /// In the shape's own object space
+    fn bounds(&self) -> BoundingBox;
+
+    fn parent_space_bounds(&self) -> BoundingBox {
+        self.bounds().transform(&self.local_transform())
+    }
+
+    fn includes(&self, object: &dyn RayInteractable) -> bool {
+        std::ptr::addr_eq(self as *const Self, object as *const dyn RayInteractable)
+    }
+
+    /// For shapes whose normals depend on the hit's `u` and `v`
+    fn normal_at_hit(&self, point: Tuple, _hit: &Intersection) -> Tuple {
+        self.normal_at(point)
+    }
+
+    fn world_to_object(&self, point: Tuple) -> Tuple {
+        let parent_point = match self.parent_transform().inverse() {
+            Some(inverse) => inverse * point,
+            None => point
+        };
+
+        match self.local_transform().inverse() {
+            Some(inverse) => inverse * parent_point,
+            None => parent_point
+        }
+    }
+
+    fn normal_to_world(&self, normal: Tuple) -> Tuple {
+        let mut normal = normal;
+
+        for transform in [self.local_transform(), self.parent_transform()] {
+            if let Some(inverse) = transform.inverse() {
+                normal = inverse.transpose() * normal;
+                normal.w = 0.0;
+                normal = normal.normalize();
+            }
+        }
+
+        normal
+    }
 }
 
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Shape {
     Sphere(Sphere),
+    Plane(Plane),
+    Cube(Cube),
+    Cylinder(Cylinder),
+    Cone(Cone),
+    Triangle(Triangle),
+    SmoothTriangle(SmoothTriangle),
+    Group(Group),
+    Csg(Csg),
 }
 
-impl RayInteractable for Shape {
-    fn intersect(&self, ray: Ray) -> Vec<Intersection> {
+impl Shape {
+    fn as_interactable(&self) -> &dyn RayInteractable {
         match self {
-            Shape::Sphere(sphere) => sphere.intersect(ray),
+            Shape::Sphere(sphere) => sphere,
+            Shape::Plane(plane) => plane,
+            Shape::Cube(cube) => cube,
+            Shape::Cylinder(cylinder) => cylinder,
+            Shape::Cone(cone) => cone,
+            Shape::Triangle(triangle) => triangle,
+            Shape::SmoothTriangle(triangle) => triangle,
+            Shape::Group(group) => group,
+            Shape::Csg(csg) => csg,
         }
     }
 
-    fn normal_at(&self, point: Tuple) -> Tuple {
+    fn as_interactable_mut(&mut self) -> &mut dyn RayInteractable {
         match self {
-            Shape::Sphere(sphere) => sphere.normal_at(point),
+            Shape::Sphere(sphere) => sphere,
+            Shape::Plane(plane) => plane,
+            Shape::Cube(cube) => cube,
+            Shape::Cylinder(cylinder) => cylinder,
+            Shape::Cone(cone) => cone,
+            Shape::Triangle(triangle) => triangle,
+            Shape::SmoothTriangle(triangle) => triangle,
+            Shape::Group(group) => group,
+            Shape::Csg(csg) => csg,
         }
     }
 
-    fn material(&self) -> Material {
+    pub fn divide(&mut self, threshold: usize) {
         match self {
-            Shape::Sphere(sphere) => sphere.material(),
+            Shape::Group(group) => group.divide(threshold),
+            Shape::Csg(csg) => csg.divide(threshold),
+            _ => {}
         }
     }
 }
 
+impl RayInteractable for Shape {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        self.as_interactable().intersect(ray)
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        self.as_interactable().normal_at(point)
+    }
+
+    fn material(&self) -> &Material {
+        self.as_interactable().material()
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.as_interactable().local_transform()
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.as_interactable().parent_transform()
+    }
 
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.as_interactable_mut().set_parent_transform(transform)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.as_interactable().bounds()
+    }
+
+    fn parent_space_bounds(&self) -> BoundingBox {
+        self.as_interactable().parent_space_bounds()
+    }
+
+    fn includes(&self, object: &dyn RayInteractable) -> bool {
+        // Hits refer to the shape inside the enum, but a hit may also have
+        // been made against the enum itself
+        std::ptr::addr_eq(self as *const Self, object as *const dyn RayInteractable)
+            || self.as_interactable().includes(object)
+    }
+
+    fn normal_at_hit(&self, point: Tuple, hit: &Intersection) -> Tuple {
+        self.as_interactable().normal_at_hit(point, hit)
+    }
+}
@@ -0,0 +1,230 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+use crate::shapes::triangle::intersect_triangle;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmoothTriangle {
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub n1: Tuple,
+    pub n2: Tuple,
+    pub n3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> Self {
+        SmoothTriangle {
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    fn barycentric(&self, point: Tuple) -> (f64, f64) {
+        let p1_to_point = point - self.p1;
+
+        let d00 = self.e1.dot(self.e1);
+        let d01 = self.e1.dot(self.e2);
+        let d11 = self.e2.dot(self.e2);
+        let d20 = p1_to_point.dot(self.e1);
+        let d21 = p1_to_point.dot(self.e2);
+
+        let denominator = d00 * d11 - d01 * d01;
+
+        (
+            (d11 * d20 - d01 * d21) / denominator,
+            (d00 * d21 - d01 * d20) / denominator,
+        )
+    }
+
+    fn interpolated_normal(&self, u: f64, v: f64) -> Tuple {
+        let object_normal = self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v);
+
+        self.normal_to_world(object_normal)
+    }
+}
+
+impl RayInteractable for SmoothTriangle {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        match intersect_triangle(transformed_ray, self.p1, self.e1, self.e2) {
+            Some((t, u, v)) => vec![Intersection::new_with_uv(t, self, u, v)],
+            None => vec![]
+        }
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let (u, v) = self.barycentric(self.world_to_object(point));
+
+        self.interpolated_normal(u, v)
+    }
+
+    fn normal_at_hit(&self, point: Tuple, hit: &Intersection) -> Tuple {
+        match (hit.u, hit.v) {
+            (Some(u), Some(v)) => self.interpolated_normal(u, v),
+            _ => self.normal_at(point)
+        }
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+
+        for point in [self.p1, self.p2, self.p3] {
+            bounds.add_point(point);
+        }
+
+        bounds
+    }
+}
+
+impl Transform for SmoothTriangle {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        SmoothTriangle {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intersection::{Intersection, Intersections};
+    use crate::ray::Ray;
+    use crate::shapes::smooth_triangle::SmoothTriangle;
+    use crate::Tuple;
+    use crate::comparison::ApproxEq;
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn constructing_a_smooth_triangle() {
+        let tri = SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0));
+
+        assert_eq!(Tuple::point(0.0, 1.0, 0.0), tri.p1);
+        assert_eq!(Tuple::point(-1.0, 0.0, 0.0), tri.p2);
+        assert_eq!(Tuple::point(1.0, 0.0, 0.0), tri.p3);
+        assert_eq!(Tuple::vector(0.0, 1.0, 0.0), tri.n1);
+        assert_eq!(Tuple::vector(-1.0, 0.0, 0.0), tri.n2);
+        assert_eq!(Tuple::vector(1.0, 0.0, 0.0), tri.n3);
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_u_and_v() {
+        let tri = SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0));
+        let r = Ray::new(Tuple::point(-0.2, 0.3, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = tri.intersect(r);
+
+        assert!(0.45.approx_eq_low_precision(xs[0].u.unwrap()));
+        assert!(0.25.approx_eq_low_precision(xs[0].v.unwrap()));
+    }
+
+    #[test]
+    fn smooth_triangle_uses_u_and_v_to_interpolate_the_normal() {
+        let tri = SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0));
+        let i = Intersection::new_with_uv(1.0, &tri, 0.45, 0.25);
+
+        let n = tri.normal_at_hit(Tuple::point(0.0, 0.0, 0.0), &i);
+
+        assert_eq!(Tuple::vector(-0.5547, 0.83205, 0.0), n);
+    }
+
+    #[test]
+    fn smooth_triangle_normal_without_hit_matches_the_interpolated_normal() {
+        let tri = SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0));
+
+        let n = tri.normal_at(Tuple::point(-0.2, 0.3, 0.0));
+
+        assert_eq!(Tuple::vector(-0.5547, 0.83205, 0.0), n);
+    }
+
+    #[test]
+    fn preparing_the_normal_on_a_smooth_triangle() {
+        let tri = SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0));
+        let i = Intersection::new_with_uv(1.0, &tri, 0.45, 0.25);
+        let r = Ray::new(Tuple::point(-0.2, 0.3, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+
+        assert_eq!(Tuple::vector(-0.5547, 0.83205, 0.0), comps.normal_v);
+    }
+}
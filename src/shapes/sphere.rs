@@ -1,13 +1,15 @@
 use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
 use crate::ray::Ray;
 use crate::{Matrix4, Transform, Tuple};
 use crate::materials::Material;
-use crate::shapes::shape_enum::{RayInteractable, Shape};
+use crate::shapes::shape_enum::RayInteractable;
 
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Sphere {
     pub transform: Matrix4,
+    parent_transform: Matrix4,
     pub material: Material,
 }
 
@@ -15,6 +17,7 @@ impl Sphere {
     pub fn new() -> Self {
         Sphere {
             transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
             material: Material::new()
         }
     }
@@ -33,7 +36,7 @@ impl Sphere {
 impl RayInteractable for Sphere {
 
     // @FIXME Possible refactor to return Intersections instead of a Vec<Intersection>
-    fn intersect(&self, ray: Ray) -> Vec<Intersection> {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
         let transformed_ray = match self.transform.inverse() {
             Some(inverse) => { ray.transform(&inverse) }
             None => { return vec![] }
@@ -49,29 +52,38 @@ impl RayInteractable for Sphere {
         if discriminant < 0.0 {
             return vec![];
         } else {
-            let t1 = Intersection::new((-b - discriminant.sqrt()) / (2.0 * a), Shape::Sphere(*self));
-            let t2 = Intersection::new((-b + discriminant.sqrt()) / (2.0 * a), Shape::Sphere(*self));
+            let t1 = Intersection::new((-b - discriminant.sqrt()) / (2.0 * a), self);
+            let t2 = Intersection::new((-b + discriminant.sqrt()) / (2.0 * a), self);
 
             vec![t1, t2]
         }
     }
 
     fn normal_at(&self, point: Tuple) -> Tuple {
-        let inverse_transform = match self.transform.inverse() {
-            Some(matrix) => matrix,
-            None => return point
-        };
-
-        let object_point = inverse_transform * point;
+        let object_point = self.world_to_object(point);
         let object_normal = (object_point - Tuple::point(0.0, 0.0, 0.0)).normalize();
-        let mut world_normal = inverse_transform.transpose() * object_normal;
-        world_normal.w = 0.0;
 
-        world_normal.normalize()
+        self.normal_to_world(object_normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
     }
 
-    fn material(&self) -> Material {
-        self.material
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
     }
 }
 
@@ -80,7 +92,7 @@ impl Transform for Sphere {
         let new_transform = *transformation * self.transform;
         Sphere {
             transform: new_transform,
-            material: self.material
+            ..self
         }
     }
 }
@@ -282,8 +294,21 @@ mod tests {
         let mut m = Material::new();
         m.ambient = 1.0;
 
-        s.material = m;
+        s.material = m.clone();
 
         assert_eq!(m, s.material)
     }
+
+    #[test]
+    fn a_sphere_has_a_bounding_box() {
+        let s = Sphere::new().with_transform(translation(1.0, -3.0, 5.0) * scaling(0.5, 2.0, 4.0));
+
+        let object_bounds = s.bounds();
+        let parent_bounds = s.parent_space_bounds();
+
+        assert_eq!(Tuple::point(-1.0, -1.0, -1.0), object_bounds.min);
+        assert_eq!(Tuple::point(1.0, 1.0, 1.0), object_bounds.max);
+        assert_eq!(Tuple::point(0.5, -5.0, 1.0), parent_bounds.min);
+        assert_eq!(Tuple::point(1.5, -1.0, 9.0), parent_bounds.max);
+    }
 }
@@ -0,0 +1,227 @@
+use crate::intersection::Intersection;
+use crate::bounds::BoundingBox;
+use crate::ray::Ray;
+use crate::{Matrix4, Transform, Tuple};
+use crate::comparison::EPSILON;
+use crate::materials::Material;
+use crate::shapes::shape_enum::RayInteractable;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triangle {
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+    pub normal: Tuple,
+    pub transform: Matrix4,
+    parent_transform: Matrix4,
+    pub material: Material,
+}
+
+impl Triangle {
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+
+        Triangle {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: (e2 * e1).normalize(),
+            transform: Matrix4::identity_matrix(),
+            parent_transform: Matrix4::identity_matrix(),
+            material: Material::new(),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+/// Möller–Trumbore intersection, returning `t`, `u` and `v`
+pub(crate) fn intersect_triangle(ray: Ray, p1: Tuple, e1: Tuple, e2: Tuple) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.direction * e2;
+    let determinant = e1.dot(dir_cross_e2);
+
+    // The ray is parallel to the triangle
+    if determinant.abs() < EPSILON {
+        return None;
+    }
+
+    let f = 1.0 / determinant;
+    let p1_to_origin = ray.origin - p1;
+    let u = f * p1_to_origin.dot(dir_cross_e2);
+
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin * e1;
+    let v = f * ray.direction.dot(origin_cross_e1);
+
+    if v < 0.0 || (u + v) > 1.0 {
+        return None;
+    }
+
+    let t = f * e2.dot(origin_cross_e1);
+
+    Some((t, u, v))
+}
+
+impl RayInteractable for Triangle {
+    fn intersect(&self, ray: Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray = match self.transform.inverse() {
+            Some(inverse) => { ray.transform(&inverse) }
+            None => { return vec![] }
+        };
+
+        match intersect_triangle(transformed_ray, self.p1, self.e1, self.e2) {
+            Some((t, u, v)) => vec![Intersection::new_with_uv(t, self, u, v)],
+            None => vec![]
+        }
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        self.normal_to_world(self.normal)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn parent_transform(&self) -> Matrix4 {
+        self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, transform: Matrix4) {
+        self.parent_transform = transform;
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+
+        for point in [self.p1, self.p2, self.p3] {
+            bounds.add_point(point);
+        }
+
+        bounds
+    }
+}
+
+impl Transform for Triangle {
+    fn transform(self, transformation: &Matrix4) -> Self {
+        let new_transform = *transformation * self.transform;
+        Triangle {
+            transform: new_transform,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ray::Ray;
+    use crate::shapes::triangle::Triangle;
+    use crate::Tuple;
+    use crate::shapes::shape_enum::RayInteractable;
+
+    #[test]
+    fn constructing_a_triangle() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+
+        assert_eq!(Tuple::vector(-1.0, -1.0, 0.0), t.e1);
+        assert_eq!(Tuple::vector(1.0, -1.0, 0.0), t.e2);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), t.normal);
+    }
+
+    #[test]
+    fn finding_the_normal_on_a_triangle() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+
+        let n1 = t.normal_at(Tuple::point(0.0, 0.5, 0.0));
+        let n2 = t.normal_at(Tuple::point(-0.5, 0.75, 0.0));
+        let n3 = t.normal_at(Tuple::point(0.5, 0.25, 0.0));
+
+        assert_eq!(t.normal, n1);
+        assert_eq!(t.normal, n2);
+        assert_eq!(t.normal, n3);
+    }
+
+    #[test]
+    fn intersecting_ray_parallel_to_the_triangle() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+        let r = Ray::new(Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert_eq!(0, t.intersect(r).len());
+    }
+
+    #[test]
+    fn ray_misses_the_triangle_edges() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+        let origins = [
+            Tuple::point(1.0, 1.0, -2.0),
+            Tuple::point(-1.0, 1.0, -2.0),
+            Tuple::point(0.0, -1.0, -2.0),
+        ];
+
+        for origin in origins {
+            let r = Ray::new(origin, Tuple::vector(0.0, 0.0, 1.0));
+
+            assert_eq!(0, t.intersect(r).len());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_a_triangle() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0));
+        let r = Ray::new(Tuple::point(0.0, 0.5, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = t.intersect(r);
+
+        assert_eq!(1, xs.len());
+        assert_eq!(2.0, xs[0].t);
+    }
+
+    #[test]
+    fn a_triangle_has_a_bounding_box() {
+        let t = Triangle::new(
+            Tuple::point(-3.0, 7.0, 2.0),
+            Tuple::point(6.0, 2.0, -4.0),
+            Tuple::point(2.0, -1.0, -1.0));
+
+        let b = t.bounds();
+
+        assert_eq!(Tuple::point(-3.0, -1.0, -4.0), b.min);
+        assert_eq!(Tuple::point(6.0, 7.0, 2.0), b.max);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Matrix4;
+use crate::{Matrix4, Tuple};
 
 pub fn translation(x: f64, y: f64, z: f64) -> Matrix4 {
     let mut result = Matrix4::identity_matrix();
@@ -70,6 +70,24 @@ pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix4
 }
 
 
+
+pub fn view_transform(from: Tuple, to: Tuple, up: Tuple) -> Matrix4 {
+    let forward = (to - from).normalize();
+    let left = forward * up.normalize();
+    let true_up = left * forward;
+
+    let orientation = Matrix4 {
+        matrix: [
+            [left.x, left.y, left.z, 0.0],
+            [true_up.x, true_up.y, true_up.z, 0.0],
+            [-forward.x, -forward.y, -forward.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ]
+    };
+
+    orientation * translation(-from.x, -from.y, -from.z)
+}
+
 pub struct TransformationBuilder<T> {
     transformation: Matrix4,
     x: T,
@@ -166,7 +184,7 @@ pub trait Transform {
 #[cfg(test)]
 mod tests {
     use crate::transformation::*;
-    use crate::Tuple;
+    use crate::{Matrix4, Tuple};
 
     use std::f64::consts;
 
@@ -416,4 +434,57 @@ mod tests {
 
         assert_eq!(actual, Tuple::point(15.0, 0.0, 7.0))
     }
+
+    #[test]
+    fn view_transform_for_default_orientation() {
+        let from = Tuple::point(0.0, 0.0, 0.0);
+        let to = Tuple::point(0.0, 0.0, -1.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        let t = view_transform(from, to, up);
+
+        assert_eq!(Matrix4::identity_matrix(), t)
+    }
+
+    #[test]
+    fn view_transform_looking_in_positive_z_direction() {
+        let from = Tuple::point(0.0, 0.0, 0.0);
+        let to = Tuple::point(0.0, 0.0, 1.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        let t = view_transform(from, to, up);
+
+        assert_eq!(scaling(-1.0, 1.0, -1.0), t)
+    }
+
+    #[test]
+    fn view_transform_moves_the_world() {
+        let from = Tuple::point(0.0, 0.0, 8.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        let t = view_transform(from, to, up);
+
+        assert_eq!(translation(0.0, 0.0, -8.0), t)
+    }
+
+    #[test]
+    fn arbitrary_view_transform() {
+        let from = Tuple::point(1.0, 3.0, 2.0);
+        let to = Tuple::point(4.0, -2.0, 8.0);
+        let up = Tuple::vector(1.0, 1.0, 0.0);
+
+        let t = view_transform(from, to, up);
+
+        let expected = Matrix4 {
+            matrix: [
+                [-0.50709, 0.50709, 0.67612, -2.36643],
+                [0.76772, 0.60609, 0.12122, -2.82843],
+                [-0.35857, 0.59761, -0.71714, 0.0],
+                [0.0, 0.0, 0.0, 1.0]
+            ]
+        };
+
+        assert_eq!(expected, t)
+    }
 }
@@ -1,29 +1,82 @@
+use std::f64::consts::PI;
 use crate::{Color, Light, Material, Ray, transformation, Tuple};
-use crate::intersection::Intersections;
+use crate::background::Background;
+use crate::bounds::BoundingBox;
+use crate::lights::{LightSample, LightSource};
+use crate::intersection::{Computations, Intersection, Intersections};
 use crate::shapes::{Shape, Sphere};
 use crate::shapes::shape_enum::RayInteractable;
 
+/// Worlds and groups with at least this many shapes, including groups
+/// inside CSG shapes, are split into a bounding volume hierarchy
+const BVH_THRESHOLD: usize = 4;
+
 pub struct World {
     pub objects: Vec<Shape>,
     pub lights: Vec<Light>,
+    /// What rays that miss every object see
+    pub background: Background,
+    /// How many directions the background is sampled in to light each hit.
+    /// With 0 the background is only seen, it doesn't light anything.
+    pub environment_samples: usize,
+    hierarchy: ObjectTree,
 }
 
 impl World {
-    pub fn new(objects: Vec<Shape>, lights: Vec<Light>) -> Self {
+    /// How many times a ray may bounce between reflective surfaces before
+    /// the rest of its path is treated as black
+    pub const RECURSION_DEPTH: usize = 5;
+
+    pub fn new(mut objects: Vec<Shape>, lights: Vec<Light>) -> Self {
+        for object in objects.iter_mut() {
+            object.divide(BVH_THRESHOLD);
+        }
+
+        let hierarchy = ObjectTree::new(&objects, (0..objects.len()).collect());
+
         World {
             objects,
             lights,
+            background: Background::default(),
+            environment_samples: 0,
+            hierarchy,
         }
     }
 
+    /// Adds a shape, dividing its groups like those passed to [`World::new`]
+    pub fn add_object(&mut self, mut object: Shape) {
+        object.divide(BVH_THRESHOLD);
+        self.objects.push(object);
+        self.rebuild_hierarchy();
+    }
+
+    /// Rebuilds the bounding volume hierarchy over the world's objects.
+    /// [`World::new`] and [`World::add_object`] keep it up to date, but
+    /// moving or removing shapes in `objects` directly needs this call.
+    /// Shapes pushed onto `objects` are always intersected, just slower.
+    pub fn rebuild_hierarchy(&mut self) {
+        self.hierarchy = ObjectTree::new(&self.objects, (0..self.objects.len()).collect());
+    }
+
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Lights diffuse surfaces with the background, sampling it in `samples`
+    /// directions per hit
+    pub fn with_environment_lighting(mut self, samples: usize) -> Self {
+        self.environment_samples = samples;
+        self
+    }
+
     pub fn create_default_world() -> Self {
-        let light = Light::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let light = Light::point(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
         let material = Material {
             color: Color::new(0.8, 1.0, 0.6),
-            ambient: 0.9,
             diffuse: 0.7,
             specular: 0.2,
-            shininess: 200.0
+            ..Material::default()
         };
         let s1 = Sphere::new().with_material(material);
         let s2 = Sphere::new().with_transform(transformation::scaling(0.5, 0.5, 0.5));
@@ -31,15 +84,148 @@ impl World {
         World::new(vec![Shape::Sphere(s1), Shape::Sphere(s2)], vec![light])
     }
 
-    pub fn intersect(&self, ray: Ray) -> Intersections {
-        let mut res = Intersections::new();
+    pub fn intersect(&self, ray: Ray) -> Intersections<'_> {
+        let mut xs = vec![];
+
+        // Shapes pushed onto `objects` since the hierarchy was built are
+        // tested one by one
+        let unsorted = if self.hierarchy.size <= self.objects.len() {
+            self.hierarchy.intersect(&self.objects, ray, &mut xs);
+            &self.objects[self.hierarchy.size..]
+        } else {
+            &self.objects[..]
+        };
+
+        xs.extend(unsorted.iter().flat_map(|shape| shape.intersect(ray)));
+
+        Intersections::new_from_vec(xs)
+    }
+
+    /// Shades a hit. `remaining` is the number of further bounces the ray
+    /// may make off reflective surfaces.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        let material = comps.object.material();
 
-        for shape in &self.objects {
-            let intersections = shape.intersect(ray);
-            res.push_vec(intersections);
+        let surface = self.lights.iter().fold(Color::black(), |color, light| {
+            let samples = self.visible_samples(comps.over_point, light);
+            color + material.lighting_samples(comps.object, *light, comps.over_point, comps.eye_v, comps.normal_v, &samples)
+        }) + self.environment_color(comps);
+
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        // Surfaces that both reflect and refract reflect more of the scene
+        // the more glancing the angle they are seen at
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    pub fn color_at(&self, ray: Ray, remaining: usize) -> Color {
+        let xs = self.intersect(ray);
+
+        match xs.hit() {
+            Some(hit) => {
+                let comps = hit.prepare_computations(ray, &xs);
+                self.shade_hit(&comps, remaining)
+            }
+            None => self.background.color_at(ray.direction)
         }
+    }
+
+    /// The diffuse light a hit receives from the background. Each sample is
+    /// weighted by how likely its direction was to be picked, so the result
+    /// converges on the true amount whichever way the directions are chosen.
+    pub fn environment_color(&self, comps: &Computations) -> Color {
+        if self.environment_samples == 0 {
+            return Color::black();
+        }
+
+        let material = comps.object.material();
+        let albedo = material.color_at(comps.object, comps.over_point) * material.diffuse;
+
+        let gathered = (0..self.environment_samples).fold(Color::black(), |sum, _| {
+            let (direction, pdf) = self.background.sample_direction(comps.normal_v);
+            let cos_theta = direction.dot(comps.normal_v);
+
+            if pdf <= 0.0 || cos_theta <= 0.0 || self.is_blocked(comps.over_point, direction, f64::INFINITY) {
+                return sum;
+            }
+
+            sum + self.background.color_at(direction) * (cos_theta / (PI * pdf))
+        });
+
+        albedo * gathered * (1.0 / self.environment_samples as f64)
+    }
+
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let reflective = comps.object.material().reflective;
+
+        if reflective == 0.0 || remaining == 0 {
+            return Color::black();
+        }
+
+        let reflect_ray = Ray::new(comps.over_point, comps.reflect_v);
 
-        res
+        self.color_at(reflect_ray, remaining - 1) * reflective
+    }
+
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let transparency = comps.object.material().transparency;
+
+        if transparency == 0.0 || remaining == 0 {
+            return Color::black();
+        }
+
+        // Snell's law: sin(theta_t) / sin(theta_i) = n1 / n2
+        let n_ratio = comps.n1 / comps.n2;
+        let cos_i = comps.eye_v.dot(comps.normal_v);
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+
+        // Total internal reflection, no light passes through
+        if sin2_t > 1.0 {
+            return Color::black();
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction = comps.normal_v * (n_ratio * cos_i - cos_t) - comps.eye_v * n_ratio;
+        let refract_ray = Ray::new(comps.under_point, direction);
+
+        self.color_at(refract_ray, remaining - 1) * transparency
+    }
+
+    /// Samples `light` from `point`, turning the samples blocked by another
+    /// object black
+    pub fn visible_samples(&self, point: Tuple, light: &Light) -> Vec<LightSample> {
+        light.samples(point)
+            .into_iter()
+            .map(|sample| if self.is_blocked(point, sample.direction, sample.distance) {
+                LightSample { intensity: Color::black(), ..sample }
+            } else {
+                sample
+            })
+            .collect()
+    }
+
+    /// Whether other objects block every sample of `light` from `point`
+    pub fn is_shadowed(&self, point: Tuple, light: &Light) -> bool {
+        light.samples(point)
+            .iter()
+            .all(|sample| self.is_blocked(point, sample.direction, sample.distance))
+    }
+
+    /// Whether an object lies within `distance` of `point` along `direction`
+    fn is_blocked(&self, point: Tuple, direction: Tuple, distance: f64) -> bool {
+        let ray = Ray::new(point, direction);
+        let xs = self.intersect(ray);
+
+        match xs.hit() {
+            Some(hit) => hit.t < distance,
+            None => false
+        }
     }
 }
 
@@ -48,6 +234,86 @@ impl Default for World {
         World {
             objects: vec![],
             lights: vec![],
+            background: Background::default(),
+            environment_samples: 0,
+            hierarchy: ObjectTree::default(),
+        }
+    }
+}
+
+/// A bounding volume hierarchy over a world's top-level objects. It refers
+/// to them by index, so the shapes themselves stay in `World::objects`.
+#[derive(Clone, Debug, Default)]
+struct ObjectTree {
+    bounds: BoundingBox,
+    /// Objects that don't fit in either child
+    objects: Vec<usize>,
+    children: Vec<ObjectTree>,
+    /// How many objects the tree holds, including those in its children
+    size: usize,
+}
+
+impl ObjectTree {
+    fn new(shapes: &[Shape], objects: Vec<usize>) -> Self {
+        let mut bounds = BoundingBox::empty();
+        for &i in objects.iter() {
+            bounds.merge(shapes[i].parent_space_bounds());
+        }
+
+        let mut tree = ObjectTree { bounds, size: objects.len(), objects, children: vec![] };
+
+        if BVH_THRESHOLD <= tree.size {
+            tree.divide(shapes);
+        }
+
+        tree
+    }
+
+    /// Splits the objects the same way as [`crate::shapes::Group::divide`]
+    fn divide(&mut self, shapes: &[Shape]) {
+        let mut finite_bounds = BoundingBox::empty();
+        for &i in self.objects.iter() {
+            let bounds = shapes[i].parent_space_bounds();
+            if bounds.is_finite() {
+                finite_bounds.merge(bounds);
+            }
+        }
+
+        if finite_bounds.is_empty() {
+            return;
+        }
+
+        let (left_bounds, right_bounds) = finite_bounds.split();
+        let (left, rest): (Vec<usize>, Vec<usize>) = self.objects
+            .iter()
+            .partition(|&&i| left_bounds.contains_box(shapes[i].parent_space_bounds()));
+        let (right, rest): (Vec<usize>, Vec<usize>) = rest
+            .into_iter()
+            .partition(|&i| right_bounds.contains_box(shapes[i].parent_space_bounds()));
+
+        if left.len() == self.size || right.len() == self.size {
+            return;
+        }
+
+        self.objects = rest;
+        self.children = [left, right]
+            .into_iter()
+            .filter(|side| !side.is_empty())
+            .map(|side| ObjectTree::new(shapes, side))
+            .collect();
+    }
+
+    fn intersect<'a>(&self, shapes: &'a [Shape], ray: Ray, xs: &mut Vec<Intersection<'a>>) {
+        if !self.bounds.intersects(ray) {
+            return;
+        }
+
+        for &i in self.objects.iter() {
+            xs.extend(shapes[i].intersect(ray));
+        }
+
+        for child in self.children.iter() {
+            child.intersect(shapes, ray, xs);
         }
     }
 }
@@ -55,8 +321,12 @@ impl Default for World {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Color, Light, Ray, scaling, Tuple};
-    use crate::shapes::{Sphere};
+    use crate::{Background, Canvas, Color, Light, Material, Ray, scaling, translation, Tuple};
+    use std::f64::consts::PI;
+    use crate::lights::AreaLight;
+    use crate::intersection::{Intersection, Intersections};
+    use crate::shapes::{Cube, Group, Plane, Shape, Sphere};
+    use crate::shapes::shape_enum::RayInteractable;
     use crate::world::World;
 
     #[test]
@@ -69,7 +339,7 @@ mod tests {
 
     #[test]
     fn creating_default_world() {
-        let light = Light::new(
+        let light = Light::point(
             Tuple::point(-10.0, 10.0, -10.0),
             Color::white());
 
@@ -99,4 +369,562 @@ mod tests {
         assert_eq!(5.5, xs[2].t);
         assert_eq!(6.0, xs[3].t);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn shading_a_hit_on_a_grouped_sphere() {
+        let sphere = Sphere::new().with_transform(scaling(0.5, 0.5, 0.5));
+        let group = Group::new()
+            .with_transform(translation(0.0, 0.0, 5.0))
+            .with_children(vec![Shape::Sphere(sphere)]);
+        let light = Light::point(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let w = World::new(vec![Shape::Group(group)], vec![light]);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = w.intersect(r);
+        let comps = xs[0].prepare_computations(r, &xs);
+
+        assert_eq!(2, xs.len());
+        assert_eq!(9.5, xs[0].t);
+        assert_eq!(Tuple::point(0.0, 0.0, 4.5), comps.point);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.normal_v);
+    }
+
+    #[test]
+    fn objects_added_to_a_world_are_divided() {
+        let spheres = [-6.0, -2.0, 2.0, 6.0]
+            .map(|x| Shape::Sphere(Sphere::new().with_transform(translation(x, 0.0, 0.0))));
+        let mut w = World::default();
+
+        w.add_object(Shape::Group(Group::new().with_children(spheres.to_vec())));
+
+        let Shape::Group(group) = &w.objects[0] else { panic!("expected a group") };
+
+        assert_eq!(2, group.children().len());
+        assert!(group.children().iter().all(|child| matches!(child, Shape::Group(_))));
+    }
+
+    #[test]
+    fn a_world_divides_its_objects() {
+        let spheres = (0..8)
+            .map(|i| Shape::Sphere(Sphere::new().with_transform(translation(i as f64 * 3.0, 0.0, 0.0))))
+            .collect();
+        let w = World::new(spheres, vec![]);
+        let r = Ray::new(Tuple::point(9.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = w.intersect(r);
+
+        assert_eq!(8, w.hierarchy.size);
+        assert!(w.hierarchy.objects.is_empty());
+        assert_eq!(2, w.hierarchy.children.len());
+        assert_eq!(2, xs.len());
+        assert!(w.objects[3].includes(xs[0].object));
+    }
+
+    #[test]
+    fn objects_pushed_onto_a_world_are_intersected() {
+        let mut w = World::create_default_world();
+        w.objects.push(Shape::Sphere(Sphere::new().with_transform(translation(0.0, 0.0, 5.0))));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert_eq!(6, w.intersect(r).len());
+
+        w.objects.remove(0);
+
+        assert_eq!(4, w.intersect(r).len());
+    }
+
+    #[test]
+    fn intersect_world_containing_a_cube() {
+        let cube = Cube::new().with_transform(translation(0.0, 0.0, 5.0));
+        let mut w = World::create_default_world();
+        w.add_object(Shape::Cube(cube));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+
+        let xs = w.intersect(r);
+
+        let Shape::Cube(cube) = &w.objects[2] else { panic!("expected a cube") };
+
+        assert_eq!(6, xs.len());
+        assert_eq!(9.0, xs[4].t);
+        assert_eq!(11.0, xs[5].t);
+        assert!(std::ptr::addr_eq(xs[4].object, cube));
+    }
+
+    #[test]
+    fn shading_an_intersection() {
+        let w = World::create_default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+        let shape = &w.objects[0];
+        let i = Intersection::new(4.0, shape);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), c);
+    }
+
+    #[test]
+    fn shading_an_intersection_from_the_inside() {
+        let mut w = World::create_default_world();
+        w.lights = vec![Light::point(Tuple::point(0.0, 0.25, 0.0), Color::white())];
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+        let shape = &w.objects[1];
+        let i = Intersection::new(0.5, shape);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.90498, 0.90498, 0.90498), c);
+    }
+
+    #[test]
+    fn shading_sums_contributions_from_every_light() {
+        let mut w = World::create_default_world();
+        let light = w.lights[0];
+        w.lights.push(light);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &w.objects[0]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855) * 2.0, c);
+    }
+
+    #[test]
+    fn color_when_a_ray_misses() {
+        let w = World::create_default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0),
+                         Tuple::vector(0.0, 1.0, 0.0));
+
+        let c = w.color_at(r, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn a_ray_that_misses_sees_the_background() {
+        let w = World::create_default_world()
+            .with_background(Background::gradient(Color::white(), Color::new(0.0, 0.0, 1.0)));
+        let up = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+        let sideways = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(1.0, 0.0, 0.0));
+
+        assert_eq!(Color::new(0.0, 0.0, 1.0), w.color_at(up, World::RECURSION_DEPTH));
+        assert_eq!(Color::new(0.5, 0.5, 1.0), w.color_at(sideways, World::RECURSION_DEPTH));
+    }
+
+    #[test]
+    fn a_solid_background_lights_a_diffuse_surface_evenly() {
+        let w = World::new(vec![Shape::Plane(Plane::new())], vec![])
+            .with_background(Background::Solid(Color::white()))
+            .with_environment_lighting(16);
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        // Without any lights only the diffuse light from the sky remains
+        assert_eq!(Color::new(0.9, 0.9, 0.9), w.color_at(r, World::RECURSION_DEPTH));
+        assert_eq!(Color::black(), w.with_environment_lighting(0).color_at(r, World::RECURSION_DEPTH));
+    }
+
+    #[test]
+    fn environment_lighting_is_blocked_by_objects() {
+        let dome = Sphere::new().with_transform(scaling(10.0, 10.0, 10.0));
+        let w = World::new(vec![Shape::Plane(Plane::new()), Shape::Sphere(dome)], vec![])
+            .with_background(Background::Solid(Color::white()))
+            .with_environment_lighting(16);
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        assert_eq!(Color::black(), w.color_at(r, World::RECURSION_DEPTH));
+    }
+
+    #[test]
+    fn importance_sampling_an_image_converges_on_its_light() {
+        let sky = Canvas::new_with_color(16, 8, Color::white());
+        let w = World::new(vec![Shape::Plane(Plane::new())], vec![])
+            .with_background(Background::image(sky))
+            .with_environment_lighting(10000);
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        let c = w.color_at(r, World::RECURSION_DEPTH);
+
+        assert!((c.r - 0.9).abs() < 0.05, "{:?}", c);
+    }
+
+    #[test]
+    fn color_when_a_ray_hits() {
+        let w = World::create_default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+
+        let c = w.color_at(r, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), c);
+    }
+
+    #[test]
+    fn color_with_an_intersection_behind_the_ray() {
+        let mut w = World::create_default_world();
+
+        for shape in w.objects.iter_mut() {
+            if let Shape::Sphere(sphere) = shape {
+                sphere.material.ambient = 1.0;
+            }
+        }
+
+        let inner = &w.objects[1];
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.75),
+                         Tuple::vector(0.0, 0.0, -1.0));
+
+        let c = w.color_at(r, World::RECURSION_DEPTH);
+
+        assert_eq!(inner.material().color, c);
+    }
+
+    #[test]
+    fn no_shadow_when_nothing_is_collinear_with_point_and_light() {
+        let w = World::create_default_world();
+        let p = Tuple::point(0.0, 10.0, 0.0);
+
+        assert!(!w.is_shadowed(p, &w.lights[0]));
+    }
+
+    #[test]
+    fn shadow_when_object_is_between_point_and_light() {
+        let w = World::create_default_world();
+        let p = Tuple::point(10.0, -10.0, 10.0);
+
+        assert!(w.is_shadowed(p, &w.lights[0]));
+    }
+
+    #[test]
+    fn a_partly_blocked_area_light_casts_no_full_shadow() {
+        let w = World::create_default_world();
+        let corner = Tuple::point(-0.5, -0.5, -5.0);
+        let v1 = Tuple::vector(1.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 1.0, 0.0);
+        let light = Light::Area(AreaLight::new(corner, v1, 2, v2, 2, Color::white()).with_jitter(false));
+
+        assert!(w.is_shadowed(Tuple::point(0.0, 0.0, 2.0), &light));
+        assert!(!w.is_shadowed(Tuple::point(1.5, 0.0, 2.0), &light));
+    }
+
+    #[test]
+    fn no_shadow_when_object_is_behind_the_light() {
+        let w = World::create_default_world();
+        let p = Tuple::point(-20.0, 20.0, -20.0);
+
+        assert!(!w.is_shadowed(p, &w.lights[0]));
+    }
+
+    #[test]
+    fn no_shadow_when_object_is_behind_the_point() {
+        let w = World::create_default_world();
+        let p = Tuple::point(-2.0, 2.0, -2.0);
+
+        assert!(!w.is_shadowed(p, &w.lights[0]));
+    }
+
+    /// The share of `light`'s samples that reach `point` unblocked
+    fn visible_fraction(w: &World, point: Tuple, light: &Light) -> f64 {
+        let samples = w.visible_samples(point, light);
+        let visible = samples.iter().filter(|sample| sample.intensity != Color::black()).count();
+
+        visible as f64 / samples.len() as f64
+    }
+
+    #[test]
+    fn point_lights_evaluate_the_light_intensity_at_a_given_point() {
+        let w = World::create_default_world();
+        let light = w.lights[0];
+        let cases = [
+            (Tuple::point(0.0, 1.0001, 0.0), 1.0),
+            (Tuple::point(-1.0001, 0.0, 0.0), 1.0),
+            (Tuple::point(0.0, 0.0, -1.0001), 1.0),
+            (Tuple::point(0.0, 0.0, 1.0001), 0.0),
+            (Tuple::point(1.0001, 0.0, 0.0), 0.0),
+            (Tuple::point(0.0, -1.0001, 0.0), 0.0),
+            (Tuple::point(0.0, 0.0, 0.0), 0.0),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, visible_fraction(&w, point, &light));
+        }
+    }
+
+    #[test]
+    fn the_area_light_intensity_function() {
+        let w = World::create_default_world();
+        let corner = Tuple::point(-0.5, -0.5, -5.0);
+        let v1 = Tuple::vector(1.0, 0.0, 0.0);
+        let v2 = Tuple::vector(0.0, 1.0, 0.0);
+        let light = Light::Area(AreaLight::new(corner, v1, 2, v2, 2, Color::white()).with_jitter(false));
+        let cases = [
+            (Tuple::point(0.0, 0.0, 2.0), 0.0),
+            (Tuple::point(1.0, -1.0, 2.0), 0.25),
+            (Tuple::point(1.5, 0.0, 2.0), 0.5),
+            (Tuple::point(1.25, 1.25, 3.0), 0.75),
+            (Tuple::point(0.0, 0.0, -2.0), 1.0),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, visible_fraction(&w, point, &light));
+        }
+    }
+
+    #[test]
+    fn a_light_without_samples_is_never_visible() {
+        let w = World::create_default_world();
+        let area = AreaLight::new(Tuple::point(-0.5, -0.5, -5.0), Tuple::vector(1.0, 0.0, 0.0), 2,
+                                  Tuple::vector(0.0, 1.0, 0.0), 2, Color::white());
+        let light = Light::Area(AreaLight { usteps: 0, ..area });
+
+        assert!(w.visible_samples(Tuple::point(0.0, 0.0, -2.0), &light).is_empty());
+    }
+
+    #[test]
+    fn directional_lights_are_blocked_by_anything_towards_the_light() {
+        let w = World::create_default_world();
+        let light = Light::directional(Tuple::vector(0.0, -1.0, 0.0), Color::white());
+
+        assert_eq!(0.0, visible_fraction(&w, Tuple::point(0.0, -100.0, 0.0), &light));
+        assert_eq!(1.0, visible_fraction(&w, Tuple::point(0.0, 1.0001, 0.0), &light));
+        assert_eq!(1.0, visible_fraction(&w, Tuple::point(2.0, -100.0, 0.0), &light));
+    }
+
+    #[test]
+    fn shading_a_hit_lit_by_a_spot_light() {
+        let floor = Plane::new();
+        let spot = Light::spot(
+            Tuple::point(0.0, 10.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 36.0,
+            PI / 18.0,
+            Color::white());
+        let w = World::new(vec![Shape::Plane(floor)], vec![spot]);
+
+        let below = w.color_at(Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)), 0);
+        let outside = w.color_at(Ray::new(Tuple::point(5.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)), 0);
+
+        assert_eq!(Color::new(1.9, 1.9, 1.9), below);
+        assert_eq!(Color::new(0.1, 0.1, 0.1), outside);
+    }
+
+    #[test]
+    fn shade_hit_is_given_an_intersection_in_shadow() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new().with_transform(translation(0.0, 0.0, 10.0));
+        let w = World::new(
+            vec![Shape::Sphere(s1), Shape::Sphere(s2)],
+            vec![Light::point(Tuple::point(0.0, 0.0, -10.0), Color::white())]);
+
+        let r = Ray::new(Tuple::point(0.0, 0.0, 5.0),
+                         Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &w.objects[1]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.1, 0.1, 0.1), c);
+    }
+
+    #[test]
+    fn reflected_color_for_a_nonreflective_material() {
+        let mut w = World::create_default_world();
+
+        if let Shape::Sphere(sphere) = &mut w.objects[1] {
+            sphere.material.ambient = 1.0;
+        }
+
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(1.0, &w.objects[1]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.reflected_color(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn reflected_color_for_a_reflective_material() {
+        let mut w = World::create_default_world();
+        let plane = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(Material { reflective: 0.5, ..Material::default() });
+        w.add_object(Shape::Plane(plane));
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let i = Intersection::new(f64::sqrt(2.0), &w.objects[2]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.reflected_color(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.19032, 0.2379, 0.14274), c);
+    }
+
+    #[test]
+    fn shade_hit_with_a_reflective_material() {
+        let mut w = World::create_default_world();
+        let plane = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(Material { reflective: 0.5, ..Material::default() });
+        w.add_object(Shape::Plane(plane));
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let i = Intersection::new(f64::sqrt(2.0), &w.objects[2]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.87677, 0.92436, 0.82918), c);
+    }
+
+    #[test]
+    fn color_at_with_mutually_reflective_surfaces() {
+        let mirror = Material { reflective: 1.0, ..Material::default() };
+        let lower = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(mirror.clone());
+        let upper = Plane::new()
+            .with_transform(translation(0.0, 1.0, 0.0))
+            .with_material(mirror);
+        let w = World::new(
+            vec![Shape::Plane(lower), Shape::Plane(upper)],
+            vec![Light::point(Tuple::point(0.0, 0.0, 0.0), Color::white())]);
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        // Terminates instead of bouncing between the planes forever
+        let c = w.color_at(r, World::RECURSION_DEPTH);
+
+        assert!(c.r > 0.0);
+    }
+
+    #[test]
+    fn reflected_color_at_the_maximum_recursive_depth() {
+        let mut w = World::create_default_world();
+        let plane = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(Material { reflective: 0.5, ..Material::default() });
+        w.add_object(Shape::Plane(plane));
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let i = Intersection::new(f64::sqrt(2.0), &w.objects[2]);
+
+        let comps = i.prepare_computations(r, &Intersections::new_from_vec(vec![i]));
+        let c = w.reflected_color(&comps, 0);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn refracted_color_with_an_opaque_surface() {
+        let w = World::create_default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(4.0, &w.objects[0]),
+            Intersection::new(6.0, &w.objects[0]),
+        ]);
+
+        let comps = xs[0].prepare_computations(r, &xs);
+        let c = w.refracted_color(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn refracted_color_at_the_maximum_recursive_depth() {
+        let mut w = World::create_default_world();
+
+        if let Shape::Sphere(sphere) = &mut w.objects[0] {
+            sphere.material.transparency = 1.0;
+            sphere.material.refractive_index = 1.5;
+        }
+
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(4.0, &w.objects[0]),
+            Intersection::new(6.0, &w.objects[0]),
+        ]);
+
+        let comps = xs[0].prepare_computations(r, &xs);
+        let c = w.refracted_color(&comps, 0);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let mut w = World::create_default_world();
+
+        if let Shape::Sphere(sphere) = &mut w.objects[0] {
+            sphere.material.transparency = 1.0;
+            sphere.material.refractive_index = 1.5;
+        }
+
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, sqrt_two), Tuple::vector(0.0, 1.0, 0.0));
+        let xs = Intersections::new_from_vec(vec![
+            Intersection::new(-sqrt_two, &w.objects[0]),
+            Intersection::new(sqrt_two, &w.objects[0]),
+        ]);
+
+        // Inside the sphere, so look at the second intersection
+        let comps = xs[1].prepare_computations(r, &xs);
+        let c = w.refracted_color(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::black(), c);
+    }
+
+    #[test]
+    fn shade_hit_with_a_transparent_material() {
+        let mut w = World::create_default_world();
+        let floor = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(Material {
+                transparency: 0.5,
+                refractive_index: 1.5,
+                ..Material::default()
+            });
+        let ball = Sphere::new()
+            .with_transform(translation(0.0, -3.5, -0.5))
+            .with_material(Material { color: Color::new(1.0, 0.0, 0.0), ambient: 0.5, ..Material::default() });
+        w.add_object(Shape::Plane(floor));
+        w.add_object(Shape::Sphere(ball));
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let xs = Intersections::new_from_vec(vec![Intersection::new(f64::sqrt(2.0), &w.objects[2])]);
+
+        let comps = xs[0].prepare_computations(r, &xs);
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.93642, 0.68642, 0.68642), c);
+    }
+
+    #[test]
+    fn shade_hit_with_a_reflective_transparent_material() {
+        let mut w = World::create_default_world();
+        let floor = Plane::new()
+            .with_transform(translation(0.0, -1.0, 0.0))
+            .with_material(Material {
+                reflective: 0.5,
+                transparency: 0.5,
+                refractive_index: 1.5,
+                ..Material::default()
+            });
+        let ball = Sphere::new()
+            .with_transform(translation(0.0, -3.5, -0.5))
+            .with_material(Material { color: Color::new(1.0, 0.0, 0.0), ambient: 0.5, ..Material::default() });
+        w.add_object(Shape::Plane(floor));
+        w.add_object(Shape::Sphere(ball));
+        let sqrt_two = f64::sqrt(2.0) / 2.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -sqrt_two, sqrt_two));
+        let xs = Intersections::new_from_vec(vec![Intersection::new(f64::sqrt(2.0), &w.objects[2])]);
+
+        let comps = xs[0].prepare_computations(r, &xs);
+        let c = w.shade_hit(&comps, World::RECURSION_DEPTH);
+
+        assert_eq!(Color::new(0.93391, 0.69643, 0.69243), c);
+    }
+}